@@ -0,0 +1,38 @@
+//! MD5 吞吐量基准测试
+//!
+//! 运行：`cargo bench --bench md5`
+
+use std::hint::black_box;
+use std::time::Instant;
+use light_tool::md5::{self, Md5};
+
+/// 对 `data` 反复计算，直到累计处理 `total` 字节，返回 MB/s
+fn throughput(data: &[u8], total: usize, mut f: impl FnMut(&[u8])) -> f64 {
+    let rounds = (total / data.len()).max(1);
+    f(data); // 预热
+    let start = Instant::now();
+    for _ in 0..rounds {
+        f(black_box(data));
+    }
+    let secs = start.elapsed().as_secs_f64();
+    (rounds * data.len()) as f64 / secs / 1_000_000.0
+}
+
+fn main() {
+    const TOTAL: usize = 256 * 1024 * 1024;
+
+    for size in [16, 64, 1024, 64 * 1024, 16 * 1024 * 1024] {
+        let data = vec![0x5au8; size];
+        let one_shot = throughput(&data, TOTAL, |d| {
+            black_box(md5::str(d));
+        });
+        let streaming = throughput(&data, TOTAL, |d| {
+            let mut md5 = Md5::new();
+            for chunk in d.chunks(4096) {
+                md5.update(chunk);
+            }
+            black_box(md5.finalize());
+        });
+        println!("md5 {:>9} bytes: one-shot {:>8.1} MB/s, streaming(4K) {:>8.1} MB/s", size, one_shot, streaming);
+    }
+}
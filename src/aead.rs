@@ -0,0 +1,532 @@
+use std::io;
+use crate::{base64, ct, random};
+
+/// 密钥长度（字节）
+pub const KEY_LEN: usize = 32;
+/// Nonce 长度（字节）
+pub const NONCE_LEN: usize = 12;
+/// 认证标签长度（字节）
+pub const TAG_LEN: usize = 16;
+
+/// 认证加密算法
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Algorithm {
+    /// ChaCha20-Poly1305 (RFC 8439)
+    ChaCha20Poly1305,
+    /// AES-256-GCM (NIST SP 800-38D)
+    Aes256Gcm,
+}
+
+impl Algorithm {
+    /// 单条消息明文的最大长度（字节），超过后块计数器回绕，密钥流重复
+    ///
+    /// AES-256-GCM 为 (2^32 - 2) × 16，ChaCha20-Poly1305 为 (2^32 - 1) × 64
+    pub const fn max_plaintext_len(self) -> u64 {
+        match self {
+            Algorithm::ChaCha20Poly1305 => (u32::MAX as u64) * 64,
+            Algorithm::Aes256Gcm => (u32::MAX as u64 - 1) * 16,
+        }
+    }
+}
+
+/// 检查明文（密文）长度不超过算法上限
+fn check_len(algorithm: Algorithm, len: usize) -> Result<(), String> {
+    if len as u64 > algorithm.max_plaintext_len() {
+        return Err(format!("Message is too long: {} bytes, at most {}", len, algorithm.max_plaintext_len()));
+    }
+    Ok(())
+}
+
+/// 使用安全随机数生成密钥
+pub fn generate_key() -> io::Result<[u8; KEY_LEN]> {
+    let mut key = [0u8; KEY_LEN];
+    random::fill_secure(&mut key)?;
+    Ok(key)
+}
+
+/// 使用安全随机数生成 Nonce
+///
+/// 同一密钥下 Nonce 绝不能重复，随机 96 位 Nonce 在单个密钥加密约 2^32 条消息内是安全的
+pub fn generate_nonce() -> io::Result<[u8; NONCE_LEN]> {
+    let mut nonce = [0u8; NONCE_LEN];
+    random::fill_secure(&mut nonce)?;
+    Ok(nonce)
+}
+
+/// 加密并认证，返回 `密文 || 标签`
+///
+/// `aad` 为附加认证数据，不加密但参与认证，解密时必须一致；明文超过 `max_plaintext_len` 时返回错误
+///
+/// # Example
+/// ```rust
+/// use light_tool::aead::{self, Algorithm};
+/// let key = aead::generate_key().unwrap();
+/// let nonce = aead::generate_nonce().unwrap();
+/// let sealed = aead::seal(Algorithm::ChaCha20Poly1305, &key, &nonce, "db.password", "s3cr3t").unwrap();
+/// let opened = aead::open(Algorithm::ChaCha20Poly1305, &key, &nonce, "db.password", &sealed).unwrap();
+/// assert_eq!(opened, b"s3cr3t")
+/// ```
+pub fn seal<A, T>(algorithm: Algorithm, key: &[u8; KEY_LEN], nonce: &[u8; NONCE_LEN], aad: A, plaintext: T) -> Result<Vec<u8>, String>
+where
+    A: AsRef<[u8]>,
+    T: AsRef<[u8]>,
+{
+    check_len(algorithm, plaintext.as_ref().len())?;
+    let mut output = plaintext.as_ref().to_vec();
+    let tag = match algorithm {
+        Algorithm::ChaCha20Poly1305 => {
+            chacha20_xor(key, 1, nonce, &mut output);
+            poly1305_tag(key, nonce, aad.as_ref(), &output)
+        }
+        Algorithm::Aes256Gcm => {
+            let aes = Aes256::new(key);
+            gcm_ctr(&aes, nonce, &mut output);
+            gcm_tag(&aes, nonce, aad.as_ref(), &output)
+        }
+    };
+    output.extend_from_slice(&tag);
+    Ok(output)
+}
+
+/// 校验并解密 `seal` 的输出，认证失败时返回错误且不输出任何明文
+pub fn open<A, T>(algorithm: Algorithm, key: &[u8; KEY_LEN], nonce: &[u8; NONCE_LEN], aad: A, sealed: T) -> Result<Vec<u8>, String>
+where
+    A: AsRef<[u8]>,
+    T: AsRef<[u8]>,
+{
+    let sealed = sealed.as_ref();
+    if sealed.len() < TAG_LEN {
+        return Err("Ciphertext is too short".to_string());
+    }
+    let (ciphertext, tag) = sealed.split_at(sealed.len() - TAG_LEN);
+    check_len(algorithm, ciphertext.len())?;
+    let mut output = ciphertext.to_vec();
+    match algorithm {
+        Algorithm::ChaCha20Poly1305 => {
+            if !ct::eq(poly1305_tag(key, nonce, aad.as_ref(), ciphertext), tag) {
+                return Err("Authentication failed".to_string());
+            }
+            chacha20_xor(key, 1, nonce, &mut output);
+        }
+        Algorithm::Aes256Gcm => {
+            let aes = Aes256::new(key);
+            if !ct::eq(gcm_tag(&aes, nonce, aad.as_ref(), ciphertext), tag) {
+                return Err("Authentication failed".to_string());
+            }
+            gcm_ctr(&aes, nonce, &mut output);
+        }
+    }
+    Ok(output)
+}
+
+/// 加密为 Base64 信封：`base64(nonce || 密文 || 标签)`，Nonce 自动随机生成
+///
+/// # Example
+/// ```rust
+/// use light_tool::aead::{self, Algorithm};
+/// let key = aead::generate_key().unwrap();
+/// let envelope = aead::encrypt(Algorithm::Aes256Gcm, &key, "api-token").unwrap();
+/// assert_eq!(aead::decrypt(Algorithm::Aes256Gcm, &key, &envelope).unwrap(), b"api-token")
+/// ```
+pub fn encrypt<T: AsRef<[u8]>>(algorithm: Algorithm, key: &[u8; KEY_LEN], plaintext: T) -> io::Result<String> {
+    let nonce = generate_nonce()?;
+    let mut envelope = nonce.to_vec();
+    let sealed = seal(algorithm, key, &nonce, [], plaintext).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    envelope.extend(sealed);
+    Ok(base64::encode_bytes(&envelope))
+}
+
+/// 解密 `encrypt` 生成的 Base64 信封
+pub fn decrypt(algorithm: Algorithm, key: &[u8; KEY_LEN], envelope: &str) -> Result<Vec<u8>, String> {
+    let envelope = base64::decode_bytes(envelope).map_err(|e| e.to_string())?;
+    if envelope.len() < NONCE_LEN {
+        return Err("Envelope is too short".to_string());
+    }
+    let (nonce, sealed) = envelope.split_at(NONCE_LEN);
+    open(algorithm, key, nonce.try_into().unwrap(), [], sealed)
+}
+
+fn quarter_round(s: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize) {
+    s[a] = s[a].wrapping_add(s[b]);
+    s[d] = (s[d] ^ s[a]).rotate_left(16);
+    s[c] = s[c].wrapping_add(s[d]);
+    s[b] = (s[b] ^ s[c]).rotate_left(12);
+    s[a] = s[a].wrapping_add(s[b]);
+    s[d] = (s[d] ^ s[a]).rotate_left(8);
+    s[c] = s[c].wrapping_add(s[d]);
+    s[b] = (s[b] ^ s[c]).rotate_left(7);
+}
+
+fn chacha20_block(key: &[u8; KEY_LEN], counter: u32, nonce: &[u8; NONCE_LEN]) -> [u8; 64] {
+    let mut state = [0u32; 16];
+    state[..4].copy_from_slice(&[0x61707865, 0x3320646e, 0x79622d32, 0x6b206574]);
+    for i in 0..8 {
+        state[4 + i] = u32::from_le_bytes(key[i * 4..i * 4 + 4].try_into().unwrap());
+    }
+    state[12] = counter;
+    for i in 0..3 {
+        state[13 + i] = u32::from_le_bytes(nonce[i * 4..i * 4 + 4].try_into().unwrap());
+    }
+
+    let mut working = state;
+    for _ in 0..10 {
+        quarter_round(&mut working, 0, 4, 8, 12);
+        quarter_round(&mut working, 1, 5, 9, 13);
+        quarter_round(&mut working, 2, 6, 10, 14);
+        quarter_round(&mut working, 3, 7, 11, 15);
+        quarter_round(&mut working, 0, 5, 10, 15);
+        quarter_round(&mut working, 1, 6, 11, 12);
+        quarter_round(&mut working, 2, 7, 8, 13);
+        quarter_round(&mut working, 3, 4, 9, 14);
+    }
+
+    let mut output = [0u8; 64];
+    for i in 0..16 {
+        output[i * 4..i * 4 + 4].copy_from_slice(&working[i].wrapping_add(state[i]).to_le_bytes());
+    }
+    output
+}
+
+fn chacha20_xor(key: &[u8; KEY_LEN], mut counter: u32, nonce: &[u8; NONCE_LEN], data: &mut [u8]) {
+    for chunk in data.chunks_mut(64) {
+        let keystream = chacha20_block(key, counter, nonce);
+        for (byte, k) in chunk.iter_mut().zip(keystream) {
+            *byte ^= k;
+        }
+        counter = counter.wrapping_add(1);
+    }
+}
+
+/// Poly1305（26 位分段实现），只处理补齐到 16 字节的完整分组
+struct Poly1305 {
+    r: [u32; 5],
+    s: [u32; 4],
+    h: [u32; 5],
+}
+
+impl Poly1305 {
+    fn new(key: &[u8]) -> Self {
+        let le = |i: usize| u32::from_le_bytes(key[i..i + 4].try_into().unwrap());
+        Poly1305 {
+            r: [
+                le(0) & 0x3ffffff,
+                (le(3) >> 2) & 0x3ffff03,
+                (le(6) >> 4) & 0x3ffc0ff,
+                (le(9) >> 6) & 0x3f03fff,
+                (le(12) >> 8) & 0x00fffff,
+            ],
+            s: [le(16), le(20), le(24), le(28)],
+            h: [0; 5],
+        }
+    }
+
+    /// 按 16 字节分组处理，末尾不足部分补零
+    fn update_padded(&mut self, data: &[u8]) {
+        for chunk in data.chunks(16) {
+            let mut block = [0u8; 16];
+            block[..chunk.len()].copy_from_slice(chunk);
+            self.block(&block);
+        }
+    }
+
+    fn block(&mut self, m: &[u8; 16]) {
+        let le = |i: usize| u32::from_le_bytes(m[i..i + 4].try_into().unwrap());
+        let [r0, r1, r2, r3, r4] = self.r.map(|r| r as u64);
+        let (s1, s2, s3, s4) = (r1 * 5, r2 * 5, r3 * 5, r4 * 5);
+
+        let h0 = (self.h[0] + (le(0) & 0x3ffffff)) as u64;
+        let h1 = (self.h[1] + ((le(3) >> 2) & 0x3ffffff)) as u64;
+        let h2 = (self.h[2] + ((le(6) >> 4) & 0x3ffffff)) as u64;
+        let h3 = (self.h[3] + ((le(9) >> 6) & 0x3ffffff)) as u64;
+        let h4 = (self.h[4] + ((le(12) >> 8) | (1 << 24))) as u64;
+
+        let d0 = h0 * r0 + h1 * s4 + h2 * s3 + h3 * s2 + h4 * s1;
+        let mut d1 = h0 * r1 + h1 * r0 + h2 * s4 + h3 * s3 + h4 * s2;
+        let mut d2 = h0 * r2 + h1 * r1 + h2 * r0 + h3 * s4 + h4 * s3;
+        let mut d3 = h0 * r3 + h1 * r2 + h2 * r1 + h3 * r0 + h4 * s4;
+        let mut d4 = h0 * r4 + h1 * r3 + h2 * r2 + h3 * r1 + h4 * r0;
+
+        d1 += d0 >> 26;
+        d2 += d1 >> 26;
+        d3 += d2 >> 26;
+        d4 += d3 >> 26;
+        let h0 = (d0 & 0x3ffffff) + (d4 >> 26) * 5;
+        let h1 = (d1 & 0x3ffffff) + (h0 >> 26);
+        self.h = [h0 & 0x3ffffff, h1, d2 & 0x3ffffff, d3 & 0x3ffffff, d4 & 0x3ffffff].map(|h| h as u32);
+    }
+
+    fn finalize(self) -> [u8; TAG_LEN] {
+        let [mut h0, mut h1, mut h2, mut h3, mut h4] = self.h;
+
+        // 完全进位
+        h2 += h1 >> 26;
+        h1 &= 0x3ffffff;
+        h3 += h2 >> 26;
+        h2 &= 0x3ffffff;
+        h4 += h3 >> 26;
+        h3 &= 0x3ffffff;
+        h0 += (h4 >> 26) * 5;
+        h4 &= 0x3ffffff;
+        h1 += h0 >> 26;
+        h0 &= 0x3ffffff;
+
+        // g = h + 5 - 2^130，h >= p 时取 g，否则取 h（无分支选择）
+        let mut g0 = h0 + 5;
+        let mut g1 = h1 + (g0 >> 26);
+        g0 &= 0x3ffffff;
+        let mut g2 = h2 + (g1 >> 26);
+        g1 &= 0x3ffffff;
+        let mut g3 = h3 + (g2 >> 26);
+        g2 &= 0x3ffffff;
+        let g4 = (h4 + (g3 >> 26)).wrapping_sub(1 << 26);
+        g3 &= 0x3ffffff;
+        let mask = (g4 >> 31).wrapping_sub(1);
+        h0 = (h0 & !mask) | (g0 & mask);
+        h1 = (h1 & !mask) | (g1 & mask);
+        h2 = (h2 & !mask) | (g2 & mask);
+        h3 = (h3 & !mask) | (g3 & mask);
+        h4 = (h4 & !mask) | (g4 & mask);
+
+        // h = h % 2^128，再加上 s
+        let words = [h0 | (h1 << 26), (h1 >> 6) | (h2 << 20), (h2 >> 12) | (h3 << 14), (h3 >> 18) | (h4 << 8)];
+        let mut tag = [0u8; TAG_LEN];
+        let mut carry = 0u64;
+        for i in 0..4 {
+            carry += words[i] as u64 + self.s[i] as u64;
+            tag[i * 4..i * 4 + 4].copy_from_slice(&(carry as u32).to_le_bytes());
+            carry >>= 32;
+        }
+        tag
+    }
+}
+
+/// RFC 8439 §2.8 的认证标签
+fn poly1305_tag(key: &[u8; KEY_LEN], nonce: &[u8; NONCE_LEN], aad: &[u8], ciphertext: &[u8]) -> [u8; TAG_LEN] {
+    let mut poly = Poly1305::new(&chacha20_block(key, 0, nonce)[..32]);
+    poly.update_padded(aad);
+    poly.update_padded(ciphertext);
+    let mut lengths = [0u8; 16];
+    lengths[..8].copy_from_slice(&(aad.len() as u64).to_le_bytes());
+    lengths[8..].copy_from_slice(&(ciphertext.len() as u64).to_le_bytes());
+    poly.block(&lengths);
+    poly.finalize()
+}
+
+/// GF(2^8) 上乘 x
+fn xtime(b: u8) -> u8 {
+    (b << 1) ^ (0x1b & 0u8.wrapping_sub(b >> 7))
+}
+
+/// GF(2^8) 乘法，不依赖秘密数据分支
+fn gmul(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0;
+    for _ in 0..8 {
+        product ^= a & 0u8.wrapping_sub(b & 1);
+        a = xtime(a);
+        b >>= 1;
+    }
+    product
+}
+
+/// AES S 盒，通过求逆（x^254）和仿射变换计算，避免查表带来的缓存时序泄露
+fn sub_byte(x: u8) -> u8 {
+    let mut square = x;
+    let mut inverse = 1;
+    for _ in 0..7 {
+        square = gmul(square, square);
+        inverse = gmul(inverse, square);
+    }
+    inverse ^ inverse.rotate_left(1) ^ inverse.rotate_left(2) ^ inverse.rotate_left(3) ^ inverse.rotate_left(4) ^ 0x63
+}
+
+/// AES-256 分组加密（仅加密方向，GCM 不需要解密）
+struct Aes256 {
+    round_keys: [[u8; 16]; 15],
+}
+
+impl Aes256 {
+    fn new(key: &[u8; KEY_LEN]) -> Self {
+        let mut words = [[0u8; 4]; 60];
+        for i in 0..8 {
+            words[i].copy_from_slice(&key[i * 4..i * 4 + 4]);
+        }
+        let mut rcon = 1u8;
+        for i in 8..60 {
+            let mut temp = words[i - 1];
+            if i % 8 == 0 {
+                temp = [sub_byte(temp[1]) ^ rcon, sub_byte(temp[2]), sub_byte(temp[3]), sub_byte(temp[0])];
+                rcon = xtime(rcon);
+            } else if i % 8 == 4 {
+                temp = temp.map(sub_byte);
+            }
+            for j in 0..4 {
+                words[i][j] = words[i - 8][j] ^ temp[j];
+            }
+        }
+
+        let mut round_keys = [[0u8; 16]; 15];
+        for (i, round_key) in round_keys.iter_mut().enumerate() {
+            for j in 0..4 {
+                round_key[j * 4..j * 4 + 4].copy_from_slice(&words[i * 4 + j]);
+            }
+        }
+        Aes256 { round_keys }
+    }
+
+    fn encrypt_block(&self, block: &[u8; 16]) -> [u8; 16] {
+        let mut state = *block;
+        xor_block(&mut state, &self.round_keys[0]);
+        for round in 1..15 {
+            // SubBytes + ShiftRows，状态按列存储：state[row + 4 * col]
+            let substituted = state.map(sub_byte);
+            for row in 0..4 {
+                for col in 0..4 {
+                    state[row + 4 * col] = substituted[row + 4 * ((col + row) % 4)];
+                }
+            }
+            if round < 14 {
+                for col in state.chunks_mut(4) {
+                    let [a0, a1, a2, a3] = [col[0], col[1], col[2], col[3]];
+                    let all = a0 ^ a1 ^ a2 ^ a3;
+                    col[0] ^= all ^ xtime(a0 ^ a1);
+                    col[1] ^= all ^ xtime(a1 ^ a2);
+                    col[2] ^= all ^ xtime(a2 ^ a3);
+                    col[3] ^= all ^ xtime(a3 ^ a0);
+                }
+            }
+            xor_block(&mut state, &self.round_keys[round]);
+        }
+        state
+    }
+}
+
+fn xor_block(block: &mut [u8; 16], other: &[u8; 16]) {
+    for (a, b) in block.iter_mut().zip(other) {
+        *a ^= b;
+    }
+}
+
+/// 计数器块：nonce || 32 位大端计数器
+fn counter_block(nonce: &[u8; NONCE_LEN], counter: u32) -> [u8; 16] {
+    let mut block = [0u8; 16];
+    block[..NONCE_LEN].copy_from_slice(nonce);
+    block[NONCE_LEN..].copy_from_slice(&counter.to_be_bytes());
+    block
+}
+
+/// GCM 的 CTR 加解密，计数器从 2 开始（1 用于加密标签）
+fn gcm_ctr(aes: &Aes256, nonce: &[u8; NONCE_LEN], data: &mut [u8]) {
+    for (i, chunk) in data.chunks_mut(16).enumerate() {
+        let keystream = aes.encrypt_block(&counter_block(nonce, (i as u32).wrapping_add(2)));
+        for (byte, k) in chunk.iter_mut().zip(keystream) {
+            *byte ^= k;
+        }
+    }
+}
+
+/// GF(2^128) 乘法（GCM 位序），固定 128 轮、无秘密相关分支
+fn gf128_mul(x: u128, y: u128) -> u128 {
+    let mut z = 0;
+    let mut v = y;
+    for i in (0..128).rev() {
+        z ^= v & 0u128.wrapping_sub((x >> i) & 1);
+        v = (v >> 1) ^ (0xe1 << 120 & 0u128.wrapping_sub(v & 1));
+    }
+    z
+}
+
+fn ghash_padded(h: u128, y: &mut u128, data: &[u8]) {
+    for chunk in data.chunks(16) {
+        let mut block = [0u8; 16];
+        block[..chunk.len()].copy_from_slice(chunk);
+        *y = gf128_mul(*y ^ u128::from_be_bytes(block), h);
+    }
+}
+
+fn gcm_tag(aes: &Aes256, nonce: &[u8; NONCE_LEN], aad: &[u8], ciphertext: &[u8]) -> [u8; TAG_LEN] {
+    let h = u128::from_be_bytes(aes.encrypt_block(&[0; 16]));
+    let mut y = 0;
+    ghash_padded(h, &mut y, aad);
+    ghash_padded(h, &mut y, ciphertext);
+    let lengths = ((aad.len() as u128 * 8) << 64) | (ciphertext.len() as u128 * 8);
+    y = gf128_mul(y ^ lengths, h);
+    let mask = u128::from_be_bytes(aes.encrypt_block(&counter_block(nonce, 1)));
+    (y ^ mask).to_be_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unhex(s: &str) -> Vec<u8> {
+        crate::hex::decode(s).unwrap()
+    }
+
+    const PLAINTEXT: &str = "Ladies and Gentlemen of the class of '99: If I could offer you only one tip for the future, sunscreen would be it.";
+
+    #[test]
+    fn test_chacha20_poly1305() {
+        // RFC 8439 §2.8.2
+        let key: [u8; 32] = core::array::from_fn(|i| 0x80 + i as u8);
+        let nonce: [u8; 12] = unhex("070000004041424344454647").try_into().unwrap();
+        let aad = unhex("50515253c0c1c2c3c4c5c6c7");
+        let expected = unhex("d31a8d34648e60db7b86afbc53ef7ec2a4aded51296e08fea9e2b5a736ee62d63dbea45e8ca9671282fafb69da92728b1a71de0a9e060b2905d6a5b67ecd3b3692ddbd7f2d778b8c9803aee328091b58fab324e4fad675945585808b4831d7bc3ff4def08e4b7a9de576d26586cec64b61161ae10b594f09e26a7e902ecbd0600691");
+
+        let sealed = seal(Algorithm::ChaCha20Poly1305, &key, &nonce, &aad, PLAINTEXT).unwrap();
+        assert_eq!(sealed, expected);
+        assert_eq!(open(Algorithm::ChaCha20Poly1305, &key, &nonce, &aad, &sealed).unwrap(), PLAINTEXT.as_bytes());
+        assert!(open(Algorithm::ChaCha20Poly1305, &key, &nonce, "", &sealed).is_err());
+    }
+
+    #[test]
+    fn test_aes256_gcm() {
+        let key: [u8; 32] = core::array::from_fn(|i| 0x80 + i as u8);
+        let nonce: [u8; 12] = unhex("070000004041424344454647").try_into().unwrap();
+        let aad = unhex("50515253c0c1c2c3c4c5c6c7");
+        let expected = unhex("7c0df61c33f0c998dbe516797c7908dcdfd52f1f10ec0b5ae2e4de9942ced85eeec8b953385268b2f9fb8414d169f7f4b24a93c0b5d29afbe1b442dc4077e8f48f22ad0a409f977cac9fcaf05be1ba04040f8b04667362fff434a71b9f2d09a3e14283372d3c5946111486e8c1a155a28965029f36e34e07302fbf985597bca58e5f");
+
+        let sealed = seal(Algorithm::Aes256Gcm, &key, &nonce, &aad, PLAINTEXT).unwrap();
+        assert_eq!(sealed, expected);
+        assert_eq!(open(Algorithm::Aes256Gcm, &key, &nonce, &aad, &sealed).unwrap(), PLAINTEXT.as_bytes());
+
+        // NIST GCM 测试用例 13、14
+        assert_eq!(seal(Algorithm::Aes256Gcm, &[0; 32], &[0; 12], "", "").unwrap(), unhex("530f8afbc74536b9a963b4f1c4cb738b"));
+        assert_eq!(
+            seal(Algorithm::Aes256Gcm, &[0; 32], &[0; 12], "", [0; 16]).unwrap(),
+            unhex("cea7403d4d606b6e074ec5d3baf39d18d0d1c8a799996bf0265b98b5d48ab919")
+        );
+    }
+
+    #[test]
+    fn test_envelope() {
+        for algorithm in [Algorithm::ChaCha20Poly1305, Algorithm::Aes256Gcm] {
+            let key = generate_key().unwrap();
+            let envelope = encrypt(algorithm, &key, "api-token").unwrap();
+            assert_ne!(envelope, encrypt(algorithm, &key, "api-token").unwrap());
+            assert_eq!(decrypt(algorithm, &key, &envelope).unwrap(), b"api-token");
+
+            // 篡改任意字节都应认证失败
+            let mut tampered = base64::decode_bytes(&envelope).unwrap();
+            tampered[NONCE_LEN] ^= 1;
+            assert!(decrypt(algorithm, &key, &base64::encode_bytes(&tampered)).is_err());
+            assert!(decrypt(algorithm, &generate_key().unwrap(), &envelope).is_err());
+            assert!(decrypt(algorithm, &key, "AAAA").is_err());
+        }
+    }
+
+    #[test]
+    fn test_max_len() {
+        // 超过上限时块计数器会回绕，必须拒绝
+        assert!(check_len(Algorithm::Aes256Gcm, 0).is_ok());
+        if let Ok(max) = usize::try_from(Algorithm::Aes256Gcm.max_plaintext_len()) {
+            assert!(check_len(Algorithm::Aes256Gcm, max).is_ok());
+            assert!(check_len(Algorithm::Aes256Gcm, max + 1).is_err());
+        }
+        if let Ok(max) = usize::try_from(Algorithm::ChaCha20Poly1305.max_plaintext_len()) {
+            assert!(check_len(Algorithm::ChaCha20Poly1305, max).is_ok());
+            assert!(check_len(Algorithm::ChaCha20Poly1305, max + 1).is_err());
+        }
+        assert_eq!(Algorithm::Aes256Gcm.max_plaintext_len(), ((1 << 32) - 2) * 16);
+        assert_eq!(Algorithm::ChaCha20Poly1305.max_plaintext_len(), ((1 << 32) - 1) * 64);
+    }
+}
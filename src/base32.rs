@@ -0,0 +1,184 @@
+pub use crate::base64::DecodeError;
+
+/// Base32 编码表 (RFC 4648 §6)
+const BASE32_CHARSET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// Crockford Base32 编码表，去掉了易混淆的 I、L、O、U
+const CROCKFORD_CHARSET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// Base32 Encode
+///
+/// Example:
+/// ```rust
+/// use light_tool::base32;
+/// assert_eq!(base32::encode("foobar"), "MZXW6YTBOI======")
+/// ```
+pub fn encode(input: &str) -> String {
+    encode_bytes(input.as_bytes())
+}
+
+/// Base32 Decode
+///
+/// Example:
+/// ```rust
+/// use light_tool::base32;
+/// assert_eq!(base32::decode("MZXW6YTBOI======").unwrap(), "foobar")
+/// ```
+pub fn decode(input: &str) -> Result<String, String> {
+    let bytes = decode_bytes(input).map_err(|e| e.to_string())?;
+    String::from_utf8(bytes).map_err(|e| e.to_string())
+}
+
+/// 对任意字节进行 Base32 编码 (RFC 4648)，带填充
+pub fn encode_bytes(bytes: &[u8]) -> String {
+    let mut output = encode_with(bytes, BASE32_CHARSET);
+    while output.len() % 8 != 0 {
+        output.push('=');
+    }
+    output
+}
+
+/// Base32 编码，不带填充（TOTP 密钥等场景使用）
+///
+/// Example:
+/// ```rust
+/// use light_tool::base32;
+/// assert_eq!(base32::encode_bytes_unpadded(b"foobar"), "MZXW6YTBOI")
+/// ```
+pub fn encode_bytes_unpadded(bytes: &[u8]) -> String {
+    encode_with(bytes, BASE32_CHARSET)
+}
+
+/// Base32 解码 (RFC 4648)，不区分大小写，填充可选，带则必须正确
+///
+/// Example:
+/// ```rust
+/// use light_tool::base32;
+/// assert_eq!(base32::decode_bytes("mzxw6ytboi").unwrap(), b"foobar");
+/// assert!(base32::decode_bytes("MZXW6YTBOI=").is_err())
+/// ```
+pub fn decode_bytes(input: &str) -> Result<Vec<u8>, DecodeError> {
+    let bytes = input.as_bytes();
+    let data_len = bytes.iter().position(|&b| b == b'=').unwrap_or(bytes.len());
+    if let Some(extra) = bytes[data_len..].iter().position(|&b| b != b'=') {
+        return Err(DecodeError::InvalidPadding { offset: data_len + extra });
+    }
+    // 有效字符数除以 8 的余数只能是 0、2、4、5、7
+    if matches!(data_len % 8, 1 | 3 | 6) {
+        return Err(DecodeError::InvalidLength { length: data_len });
+    }
+    let padding = bytes.len() - data_len;
+    if padding != 0 && (bytes.len() % 8 != 0 || padding >= 8) {
+        return Err(DecodeError::InvalidPadding { offset: data_len });
+    }
+
+    decode_with(bytes[..data_len].iter().copied().enumerate(), |byte| match byte.to_ascii_uppercase() {
+        c @ b'A'..=b'Z' => Some(c - b'A'),
+        c @ b'2'..=b'7' => Some(c - b'2' + 26),
+        _ => None,
+    })
+}
+
+/// Crockford Base32 编码，不带填充
+///
+/// Example:
+/// ```rust
+/// use light_tool::base32;
+/// assert_eq!(base32::crockford_encode(b"foobar"), "CSQPYRK1E8")
+/// ```
+pub fn crockford_encode<T: AsRef<[u8]>>(input: T) -> String {
+    encode_with(input.as_ref(), CROCKFORD_CHARSET)
+}
+
+/// Crockford Base32 解码，不区分大小写，`I`/`L` 视为 `1`，`O` 视为 `0`，忽略 `-`
+///
+/// Example:
+/// ```rust
+/// use light_tool::base32;
+/// assert_eq!(base32::crockford_decode("csqp-yrkl-e8").unwrap(), b"foobar")
+/// ```
+pub fn crockford_decode(input: &str) -> Result<Vec<u8>, DecodeError> {
+    let symbols: Vec<(usize, u8)> = input.bytes().enumerate().filter(|&(_, b)| b != b'-').collect();
+    if matches!(symbols.len() % 8, 1 | 3 | 6) {
+        return Err(DecodeError::InvalidLength { length: symbols.len() });
+    }
+
+    decode_with(symbols.into_iter(), |byte| match byte.to_ascii_uppercase() {
+        b'O' => Some(0),
+        b'I' | b'L' => Some(1),
+        c => CROCKFORD_CHARSET.iter().position(|&s| s == c).map(|value| value as u8),
+    })
+}
+
+fn encode_with(bytes: &[u8], charset: &[u8; 32]) -> String {
+    let mut output = String::with_capacity(bytes.len().div_ceil(5) * 8);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for &byte in bytes {
+        buffer = (buffer << 8) | byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            output.push(charset[((buffer >> bits) & 0x1f) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        output.push(charset[((buffer << (5 - bits)) & 0x1f) as usize] as char);
+    }
+    output
+}
+
+/// 按 (偏移, 字符) 逐个解码，偏移用于错误定位
+fn decode_with<I>(symbols: I, value: impl Fn(u8) -> Option<u8>) -> Result<Vec<u8>, DecodeError>
+where
+    I: ExactSizeIterator<Item = (usize, u8)>,
+{
+    let mut output = Vec::with_capacity(symbols.len() * 5 / 8);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for (offset, byte) in symbols {
+        let value = value(byte).ok_or(DecodeError::InvalidByte { offset, byte })?;
+        buffer = (buffer << 5) | value as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            output.push((buffer >> bits) as u8);
+        }
+    }
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rfc4648() {
+        let cases = [
+            ("", ""),
+            ("f", "MY======"),
+            ("fo", "MZXQ===="),
+            ("foo", "MZXW6==="),
+            ("foob", "MZXW6YQ="),
+            ("fooba", "MZXW6YTB"),
+            ("foobar", "MZXW6YTBOI======"),
+        ];
+        for (plain, encoded) in cases {
+            assert_eq!(encode(plain), encoded);
+            assert_eq!(decode(encoded).unwrap(), plain);
+            assert_eq!(decode_bytes(encoded.trim_end_matches('=')).unwrap(), plain.as_bytes());
+        }
+        assert_eq!(decode_bytes("MZXW6Y1B"), Err(DecodeError::InvalidByte { offset: 6, byte: b'1' }));
+        assert_eq!(decode_bytes("MZXW6YTBO"), Err(DecodeError::InvalidLength { length: 9 }));
+        assert_eq!(decode_bytes("MY==MY=="), Err(DecodeError::InvalidPadding { offset: 4 }));
+        assert_eq!(decode_bytes("MZXW6YTB========"), Err(DecodeError::InvalidPadding { offset: 8 }));
+    }
+
+    #[test]
+    fn test_crockford() {
+        assert_eq!(crockford_encode(b"foobar"), "CSQPYRK1E8");
+        assert_eq!(crockford_decode("CSQPYRK1E8").unwrap(), b"foobar");
+        assert_eq!(crockford_decode("csqpyrkie8").unwrap(), b"foobar");
+        assert_eq!(crockford_decode("CSQPYRKUE8"), Err(DecodeError::InvalidByte { offset: 7, byte: b'U' }));
+    }
+}
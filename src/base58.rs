@@ -0,0 +1,158 @@
+use crate::digest::Digest;
+use crate::hmac::Sha256;
+pub use crate::base64::DecodeError;
+
+/// Base58 编码表（Bitcoin），去掉了易混淆的 0、O、I、l
+const BASE58_CHARSET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Base58Check 校验和长度
+const CHECKSUM_LEN: usize = 4;
+
+/// Base58 Encode
+///
+/// Example:
+/// ```rust
+/// use light_tool::base58;
+/// assert_eq!(base58::encode("Hello World!"), "2NEpo7TZRRrLZSi2U")
+/// ```
+pub fn encode(input: &str) -> String {
+    encode_bytes(input.as_bytes())
+}
+
+/// Base58 Decode
+///
+/// Example:
+/// ```rust
+/// use light_tool::base58;
+/// assert_eq!(base58::decode("2NEpo7TZRRrLZSi2U").unwrap(), "Hello World!")
+/// ```
+pub fn decode(input: &str) -> Result<String, String> {
+    let bytes = decode_bytes(input).map_err(|e| e.to_string())?;
+    String::from_utf8(bytes).map_err(|e| e.to_string())
+}
+
+/// 对任意字节进行 Base58 编码，开头的每个零字节编码为 `1`
+pub fn encode_bytes(bytes: &[u8]) -> String {
+    encode_radix(bytes, BASE58_CHARSET)
+}
+
+/// Base58 解码为任意字节
+pub fn decode_bytes(input: &str) -> Result<Vec<u8>, DecodeError> {
+    decode_radix(input, BASE58_CHARSET)
+}
+
+/// Base58Check 编码：在数据后附加 `SHA256(SHA256(data))` 的前 4 字节作为校验和
+///
+/// Example:
+/// ```rust
+/// use light_tool::base58;
+/// let token = base58::encode_check(b"order-20240101-0001");
+/// assert_eq!(base58::decode_check(&token).unwrap(), b"order-20240101-0001")
+/// ```
+pub fn encode_check(bytes: &[u8]) -> String {
+    let mut data = bytes.to_vec();
+    data.extend_from_slice(&checksum(bytes));
+    encode_bytes(&data)
+}
+
+/// Base58Check 解码并校验
+pub fn decode_check(input: &str) -> Result<Vec<u8>, DecodeError> {
+    let mut data = decode_bytes(input)?;
+    if data.len() < CHECKSUM_LEN {
+        return Err(DecodeError::InvalidChecksum);
+    }
+    let payload_len = data.len() - CHECKSUM_LEN;
+    if data[payload_len..] != checksum(&data[..payload_len]) {
+        return Err(DecodeError::InvalidChecksum);
+    }
+    data.truncate(payload_len);
+    Ok(data)
+}
+
+fn checksum(bytes: &[u8]) -> [u8; CHECKSUM_LEN] {
+    Sha256::digest(Sha256::digest(bytes))[..CHECKSUM_LEN].try_into().unwrap()
+}
+
+/// 将字节视为大端整数转换为指定进制，开头的零字节保留为编码表的第一个字符
+///
+/// 复杂度为 O(n²)，适用于令牌、ID 等短数据
+pub(crate) fn encode_radix(bytes: &[u8], charset: &[u8]) -> String {
+    let base = charset.len() as u32;
+    let zeros = bytes.iter().take_while(|&&b| b == 0).count();
+
+    // 小端存储的各位数字
+    let mut digits: Vec<u8> = Vec::with_capacity(bytes.len() * 138 / 100 + 1);
+    for &byte in &bytes[zeros..] {
+        let mut carry = byte as u32;
+        for digit in digits.iter_mut() {
+            carry += (*digit as u32) << 8;
+            *digit = (carry % base) as u8;
+            carry /= base;
+        }
+        while carry > 0 {
+            digits.push((carry % base) as u8);
+            carry /= base;
+        }
+    }
+
+    let mut output = String::with_capacity(zeros + digits.len());
+    output.extend(std::iter::repeat(charset[0] as char).take(zeros));
+    output.extend(digits.iter().rev().map(|&d| charset[d as usize] as char));
+    output
+}
+
+/// `encode_radix` 的逆运算
+pub(crate) fn decode_radix(input: &str, charset: &[u8]) -> Result<Vec<u8>, DecodeError> {
+    let base = charset.len() as u32;
+    let zeros = input.bytes().take_while(|&b| b == charset[0]).count();
+
+    // 小端存储的各字节
+    let mut bytes: Vec<u8> = Vec::with_capacity(input.len());
+    for (offset, byte) in input.bytes().enumerate() {
+        let value = charset.iter().position(|&c| c == byte).ok_or(DecodeError::InvalidByte { offset, byte })?;
+        let mut carry = value as u32;
+        for b in bytes.iter_mut() {
+            carry += *b as u32 * base;
+            *b = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push(carry as u8);
+            carry >>= 8;
+        }
+    }
+
+    let mut output = vec![0u8; zeros];
+    output.extend(bytes.iter().rev());
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_decode() {
+        assert_eq!(encode(""), "");
+        assert_eq!(encode_bytes(&[0, 0, 1, 2]), "115T");
+        assert_eq!(decode_bytes("115T").unwrap(), [0, 0, 1, 2]);
+        assert_eq!(decode_bytes("1").unwrap(), [0]);
+        assert_eq!(decode_bytes("2NEpo7TZ0"), Err(DecodeError::InvalidByte { offset: 8, byte: b'0' }));
+
+        let bytes: Vec<u8> = (0..=255).collect();
+        assert_eq!(decode_bytes(&encode_bytes(&bytes)).unwrap(), bytes);
+    }
+
+    #[test]
+    fn test_check() {
+        // Bitcoin 地址：版本字节 0x00 + HASH160
+        let payload = [
+            0x00, 0x01, 0x09, 0x66, 0x77, 0x60, 0x06, 0x95, 0x3d, 0x55, 0x67,
+            0x43, 0x9e, 0x5e, 0x39, 0xf8, 0x6a, 0x0d, 0x27, 0x3b, 0xee,
+        ];
+        assert_eq!(encode_check(&payload), "16UwLL9Risc3QfPqBUvKofHmBQ7wMtjvM");
+        assert_eq!(decode_check("16UwLL9Risc3QfPqBUvKofHmBQ7wMtjvM").unwrap(), payload);
+        assert_eq!(decode_check("16UwLL9Risc3QfPqBUvKofHmBQ7wMtjvN"), Err(DecodeError::InvalidChecksum));
+        assert_eq!(decode_check("1"), Err(DecodeError::InvalidChecksum));
+    }
+}
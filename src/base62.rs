@@ -0,0 +1,100 @@
+use crate::base58::{decode_radix, encode_radix};
+pub use crate::base64::DecodeError;
+
+/// Base62 编码表（0-9、A-Z、a-z）
+const BASE62_CHARSET: &[u8; 62] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// Base62 Encode
+///
+/// Example:
+/// ```rust
+/// use light_tool::base62;
+/// assert_eq!(base62::encode("Hello World!"), "T8dgcjRGkZ3aysdN")
+/// ```
+pub fn encode(input: &str) -> String {
+    encode_bytes(input.as_bytes())
+}
+
+/// Base62 Decode
+///
+/// Example:
+/// ```rust
+/// use light_tool::base62;
+/// assert_eq!(base62::decode("T8dgcjRGkZ3aysdN").unwrap(), "Hello World!")
+/// ```
+pub fn decode(input: &str) -> Result<String, String> {
+    let bytes = decode_bytes(input).map_err(|e| e.to_string())?;
+    String::from_utf8(bytes).map_err(|e| e.to_string())
+}
+
+/// 对任意字节进行 Base62 编码，开头的每个零字节编码为 `0`
+pub fn encode_bytes(bytes: &[u8]) -> String {
+    encode_radix(bytes, BASE62_CHARSET)
+}
+
+/// Base62 解码为任意字节
+pub fn decode_bytes(input: &str) -> Result<Vec<u8>, DecodeError> {
+    decode_radix(input, BASE62_CHARSET)
+}
+
+/// 将整数编码为紧凑的 Base62 字符串，适合自增 ID 等场景
+///
+/// Example:
+/// ```rust
+/// use light_tool::base62;
+/// assert_eq!(base62::encode_u64(1234567890), "1LY7VK");
+/// assert_eq!(base62::decode_u64("1LY7VK").unwrap(), 1234567890)
+/// ```
+pub fn encode_u64(mut value: u64) -> String {
+    let mut digits = Vec::new();
+    loop {
+        digits.push(BASE62_CHARSET[(value % 62) as usize]);
+        value /= 62;
+        if value == 0 {
+            break;
+        }
+    }
+    digits.iter().rev().map(|&d| d as char).collect()
+}
+
+/// 解码 `encode_u64` 的结果，超出 `u64` 范围时返回 `Overflow`
+pub fn decode_u64(input: &str) -> Result<u64, DecodeError> {
+    if input.is_empty() {
+        return Err(DecodeError::InvalidLength { length: 0 });
+    }
+    let mut value = 0u64;
+    for (offset, byte) in input.bytes().enumerate() {
+        let digit = BASE62_CHARSET.iter().position(|&c| c == byte).ok_or(DecodeError::InvalidByte { offset, byte })?;
+        value = value
+            .checked_mul(62)
+            .and_then(|v| v.checked_add(digit as u64))
+            .ok_or(DecodeError::Overflow)?;
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_decode() {
+        assert_eq!(encode_bytes(&[0, 0xff]), "047");
+        assert_eq!(decode_bytes("047").unwrap(), [0, 0xff]);
+        assert_eq!(decode_bytes("T8dg-"), Err(DecodeError::InvalidByte { offset: 4, byte: b'-' }));
+
+        let bytes: Vec<u8> = (0..=255).rev().collect();
+        assert_eq!(decode_bytes(&encode_bytes(&bytes)).unwrap(), bytes);
+    }
+
+    #[test]
+    fn test_u64() {
+        assert_eq!(encode_u64(0), "0");
+        assert_eq!(encode_u64(61), "z");
+        assert_eq!(encode_u64(62), "10");
+        assert_eq!(encode_u64(u64::MAX), "LygHa16AHYF");
+        assert_eq!(decode_u64("LygHa16AHYF").unwrap(), u64::MAX);
+        assert_eq!(decode_u64("LygHa16AHYG"), Err(DecodeError::Overflow));
+        assert!(decode_u64("").is_err());
+    }
+}
@@ -1,44 +1,261 @@
+use std::fmt;
+use std::io::{self, Read, Write};
 
 /// Base64 编码表
-const BASE64_CHARSET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Alphabet {
+    symbols: [u8; 64],
+    /// 字符到 6 位值的反查表，0xff 表示非法字符
+    values: [u8; 256],
+}
 
-/// Base64 Encode
+impl Alphabet {
+    /// 标准编码表 (RFC 4648 §4)
+    pub const STANDARD: Alphabet = Alphabet::build(b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/");
+    /// URL 安全的编码表 (RFC 4648 §5)
+    pub const URL_SAFE: Alphabet = Alphabet::build(b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_");
+    /// bcrypt 使用的编码表
+    pub const BCRYPT: Alphabet = Alphabet::build(b"./ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789");
+    /// crypt(3) 使用的编码表（仅字符集，位序与标准 Base64 相同）
+    pub const CRYPT: Alphabet = Alphabet::build(b"./0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz");
+
+    /// 自定义编码表，要求 64 个互不相同的可打印 ASCII 字符，且不含 `=`
+    ///
+    /// Example:
+    /// ```rust
+    /// use light_tool::base64::{Alphabet, Engine, Padding};
+    /// let alphabet = Alphabet::new("0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz-_").unwrap();
+    /// let engine = Engine::new(alphabet, Padding::Forbidden);
+    /// assert_eq!(engine.encode("Hi"), "I6a");
+    /// assert!(Alphabet::new("ABC").is_err())
+    /// ```
+    pub fn new(symbols: &str) -> Result<Alphabet, String> {
+        let symbols: &[u8; 64] = symbols
+            .as_bytes()
+            .try_into()
+            .map_err(|_| format!("Base64 alphabet must have 64 symbols, got {}", symbols.len()))?;
+        for (i, &symbol) in symbols.iter().enumerate() {
+            if !symbol.is_ascii_graphic() || symbol == b'=' {
+                return Err(format!("Invalid symbol in Base64 alphabet: {:?}", symbol as char));
+            }
+            if symbols[..i].contains(&symbol) {
+                return Err(format!("Duplicate symbol in Base64 alphabet: {:?}", symbol as char));
+            }
+        }
+        Ok(Alphabet::build(symbols))
+    }
+
+    const fn build(symbols: &[u8; 64]) -> Alphabet {
+        let mut values = [0xff; 256];
+        let mut i = 0;
+        while i < 64 {
+            values[symbols[i] as usize] = i as u8;
+            i += 1;
+        }
+        Alphabet { symbols: *symbols, values }
+    }
+}
+
+/// 填充策略
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Padding {
+    /// 编码时填充，解码时必须带有正确的填充
+    Required,
+    /// 编码时填充，解码时可带可不带，带则必须正确
+    Optional,
+    /// 编码时不填充，解码时不允许出现 `=`
+    Forbidden,
+}
+
+/// Base64 编解码器，由编码表与填充策略组成
 ///
 /// Example:
 /// ```rust
-/// use light_tool::base64;
-/// println!("base64 encode: {}", base64::encode("Hello, World!"))
+/// use light_tool::base64::{self, Alphabet, Engine, Padding};
+/// assert_eq!(base64::URL_SAFE_NO_PAD.encode([0xfb, 0xff]), "-_8");
+///
+/// let strict = Engine::new(Alphabet::STANDARD, Padding::Required);
+/// assert_eq!(strict.decode("-_8="), Err(base64::DecodeError::InvalidByte { offset: 0, byte: b'-' }));
+/// assert!(strict.decode("+/8").is_err());
+/// assert_eq!(strict.decode("+/8=").unwrap(), [0xfb, 0xff])
 /// ```
-pub fn encode(input: &str) -> String {
-    let bytes = input.as_bytes();
-    let mut output = Vec::new();
-    let mut buffer = 0u32;
-    let mut bits_collected = 0;
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Engine {
+    alphabet: Alphabet,
+    padding: Padding,
+}
 
-    for &byte in bytes {
-        buffer = (buffer << 8) | (byte as u32);
-        bits_collected += 8;
+/// 标准编码表，解码时填充可选
+pub const STANDARD: Engine = Engine::new(Alphabet::STANDARD, Padding::Optional);
+/// 标准编码表，不填充
+pub const STANDARD_NO_PAD: Engine = Engine::new(Alphabet::STANDARD, Padding::Forbidden);
+/// URL 安全编码表，解码时填充可选
+pub const URL_SAFE: Engine = Engine::new(Alphabet::URL_SAFE, Padding::Optional);
+/// URL 安全编码表，不填充（JWT 等场景使用）
+pub const URL_SAFE_NO_PAD: Engine = Engine::new(Alphabet::URL_SAFE, Padding::Forbidden);
+/// bcrypt 编码表，不填充
+pub const BCRYPT: Engine = Engine::new(Alphabet::BCRYPT, Padding::Forbidden);
+/// crypt(3) 编码表，不填充
+pub const CRYPT: Engine = Engine::new(Alphabet::CRYPT, Padding::Forbidden);
 
-        while bits_collected >= 6 {
-            bits_collected -= 6;
-            let index = (buffer >> bits_collected) & 0b111111; // 取出高6位
-            output.push(BASE64_CHARSET[index as usize]);
+impl Engine {
+    pub const fn new(alphabet: Alphabet, padding: Padding) -> Engine {
+        Engine { alphabet, padding }
+    }
+
+    /// 编码
+    pub fn encode<T: AsRef<[u8]>>(&self, input: T) -> String {
+        let input = input.as_ref();
+        let mut output = Vec::with_capacity(input.len().div_ceil(3) * 4);
+        let mut buffer = 0u32;
+        let mut bits_collected = 0;
+
+        for &byte in input {
+            buffer = (buffer << 8) | (byte as u32);
+            bits_collected += 8;
+
+            while bits_collected >= 6 {
+                bits_collected -= 6;
+                let index = (buffer >> bits_collected) & 0b111111; // 取出高6位
+                output.push(self.alphabet.symbols[index as usize]);
+            }
         }
+
+        // 如果有剩余位数，不足6位的部分补0
+        if bits_collected > 0 {
+            buffer <<= 6 - bits_collected; // 左移补零
+            let index = buffer & 0b111111;
+            output.push(self.alphabet.symbols[index as usize]);
+        }
+
+        // 添加必要的填充 '='
+        while self.padding != Padding::Forbidden && output.len() % 4 != 0 {
+            output.push(b'=');
+        }
+
+        String::from_utf8(output).unwrap()
     }
 
-    // 如果有剩余位数，不足6位的部分补0
-    if bits_collected > 0 {
-        buffer <<= 6 - bits_collected; // 左移补零
-        let index = buffer & 0b111111;
-        output.push(BASE64_CHARSET[index as usize]);
+    /// 解码，填充按引擎的策略校验
+    pub fn decode(&self, input: &str) -> Result<Vec<u8>, DecodeError> {
+        self.decode_with(input, DecodeMode::Standard)
     }
 
-    // 添加必要的填充 '='
-    while output.len() % 4 != 0 {
-        output.push(b'=');
+    /// 按指定模式解码
+    ///
+    /// Example:
+    /// ```rust
+    /// use light_tool::base64::{self, DecodeError, DecodeMode};
+    /// assert_eq!(base64::STANDARD.decode_with("SGk=\r\nSGk=", DecodeMode::Lenient), Err(DecodeError::InvalidPadding { offset: 6 }));
+    /// assert_eq!(base64::STANDARD.decode_with("SGVs\r\nbG8=", DecodeMode::Lenient).unwrap(), b"Hello");
+    /// assert_eq!(base64::STANDARD.decode_with("SGl=", DecodeMode::Strict), Err(DecodeError::InvalidTrailingBits { offset: 2 }));
+    /// assert_eq!(base64::STANDARD.decode_with("SGk", DecodeMode::Strict), Err(DecodeError::InvalidPadding { offset: 3 }))
+    /// ```
+    pub fn decode_with(&self, input: &str, mode: DecodeMode) -> Result<Vec<u8>, DecodeError> {
+        let input = input.as_bytes();
+        let skip = |byte: u8| mode == DecodeMode::Lenient && byte.is_ascii_whitespace();
+        let mut output = Vec::with_capacity(input.len() / 4 * 3);
+        let mut buffer = 0u32;
+        let mut bits_collected = 0;
+        let mut symbols = 0;
+        let mut last_symbol = 0;
+        let mut padding = None;
+
+        for (offset, &byte) in input.iter().enumerate() {
+            if skip(byte) {
+                continue;
+            }
+            if byte == b'=' {
+                // 填充之后只允许继续出现填充
+                if let Some(extra) = input[offset..].iter().position(|&b| b != b'=' && !skip(b)) {
+                    return Err(DecodeError::InvalidPadding { offset: offset + extra });
+                }
+                padding = Some(offset);
+                break;
+            }
+
+            let value = self.alphabet.values[byte as usize];
+            if value == 0xff {
+                return Err(DecodeError::InvalidByte { offset, byte });
+            }
+
+            buffer = (buffer << 6) | (value as u32);
+            bits_collected += 6;
+            symbols += 1;
+            last_symbol = offset;
+
+            if bits_collected >= 8 {
+                bits_collected -= 8;
+                output.push((buffer >> bits_collected) as u8);
+                buffer &= (1 << bits_collected) - 1; // 保留剩余位
+            }
+        }
+
+        if symbols % 4 == 1 {
+            return Err(DecodeError::InvalidLength { length: symbols });
+        }
+
+        let expected = (4 - symbols % 4) % 4;
+        let actual = padding.map_or(0, |offset| input[offset..].iter().filter(|&&b| b == b'=').count());
+        let valid = match (mode, self.padding) {
+            (DecodeMode::Lenient, _) | (DecodeMode::Standard, Padding::Optional) => actual == 0 || actual == expected,
+            (_, Padding::Forbidden) => actual == 0,
+            _ => actual == expected,
+        };
+        if !valid {
+            return Err(DecodeError::InvalidPadding { offset: padding.unwrap_or(input.len()) });
+        }
+
+        // 末尾不足一个字节的剩余位必须为 0，保证编码唯一
+        if mode == DecodeMode::Strict && buffer != 0 {
+            return Err(DecodeError::InvalidTrailingBits { offset: last_symbol });
+        }
+        Ok(output)
     }
+}
+
+/// 解码模式
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DecodeMode {
+    /// 按引擎的填充策略校验，忽略末尾多余的位
+    Standard,
+    /// RFC 4648 严格校验：填充必须规范（引擎禁止填充时不得出现），末尾多余的位必须为 0
+    Strict,
+    /// MIME (RFC 2045) 宽松模式：跳过空白与换行，填充可选，忽略末尾多余的位
+    Lenient,
+}
+
+/// Base64 Encode
+///
+/// Example:
+/// ```rust
+/// use light_tool::base64;
+/// println!("base64 encode: {}", base64::encode("Hello, World!"))
+/// ```
+pub fn encode(input: &str) -> String {
+    encode_bytes(input.as_bytes())
+}
+
+/// 对任意字节进行 Base64 编码
+///
+/// Example:
+/// ```rust
+/// use light_tool::base64;
+/// assert_eq!(base64::encode_bytes(&[0x89, b'P', b'N', b'G']), "iVBORw==")
+/// ```
+pub fn encode_bytes(bytes: &[u8]) -> String {
+    STANDARD.encode(bytes)
+}
 
-    String::from_utf8(output).unwrap()
+/// URL 安全的 Base64 编码，不带填充（JWT 等场景使用）
+///
+/// Example:
+/// ```rust
+/// use light_tool::base64;
+/// assert_eq!(base64::url_encode([0xfb, 0xff]), "-_8")
+/// ```
+pub fn url_encode<T: AsRef<[u8]>>(input: T) -> String {
+    URL_SAFE_NO_PAD.encode(input)
 }
 
 /// Base64 Decode
@@ -49,35 +266,293 @@ pub fn encode(input: &str) -> String {
 /// println!("base64 decode: {}", base64::decode("SGVsbG8sIFdvcmxkIQ==").unwrap())
 /// ```
 pub fn decode(input: &str) -> Result<String, String> {
-    let mut output = Vec::new();
-    let mut buffer = 0u32;
-    let mut bits_collected = 0;
-
-    for &byte in input.as_bytes() {
-        if byte == b'=' {
-            break; // 跳过填充字符
-        }
-
-        let value = match byte {
-            b'A'..=b'Z' => byte - b'A',
-            b'a'..=b'z' => byte - b'a' + 26,
-            b'0'..=b'9' => byte - b'0' + 52,
-            b'+' => 62,
-            b'/' => 63,
-            _ => return Err(format!("Invalid character in Base64 string: {}", byte)),
-        };
+    let bytes = decode_bytes(input).map_err(|e| e.to_string())?;
+    String::from_utf8(bytes).map_err(|e| e.to_string())
+}
 
-        buffer = (buffer << 6) | (value as u32);
-        bits_collected += 6;
+/// 解码错误，`base32`、`base58`、`base62`、`base85`、`hex`、`percent` 共用
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    /// 非法字符，`offset` 为其在输入中的字节偏移
+    InvalidByte { offset: usize, byte: u8 },
+    /// 有效字符数无法还原出完整字节（如 Base64 除以 4 余 1、十六进制为奇数），`length` 为有效字符数
+    InvalidLength { length: usize },
+    /// 填充不符合填充策略，或填充 `=` 之后出现了非填充字符
+    InvalidPadding { offset: usize },
+    /// 严格模式下最后一个字符携带了非零的多余位
+    InvalidTrailingBits { offset: usize },
+    /// 校验和不匹配（Base58Check）
+    InvalidChecksum,
+    /// 解码出的整数超出目标类型的范围（`base62::decode_u64`）
+    Overflow,
+}
 
-        if bits_collected >= 8 {
-            bits_collected -= 8;
-            output.push((buffer >> bits_collected) as u8);
-            buffer &= (1 << bits_collected) - 1; // 保留剩余位
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::InvalidByte { offset, byte } => {
+                write!(f, "Invalid character at offset {}: {:?}", offset, *byte as char)
+            }
+            DecodeError::InvalidLength { length } => write!(f, "Invalid length: {} symbols", length),
+            DecodeError::InvalidPadding { offset } => write!(f, "Invalid padding at offset {}", offset),
+            DecodeError::InvalidTrailingBits { offset } => write!(f, "Non-zero trailing bits at offset {}", offset),
+            DecodeError::InvalidChecksum => f.write_str("Invalid checksum"),
+            DecodeError::Overflow => f.write_str("Decoded value overflows the target integer type"),
         }
     }
+}
+
+impl std::error::Error for DecodeError {}
 
-    String::from_utf8(output).map_err(|e| e.to_string())
+impl DecodeError {
+    /// 分段解码时换算为整个输入中的位置：偏移经 `position` 映射，`InvalidLength` 加上之前已解码的字符数
+    fn relocate(self, position: impl Fn(usize) -> usize, symbols: usize) -> DecodeError {
+        match self {
+            DecodeError::InvalidByte { offset, byte } => DecodeError::InvalidByte { offset: position(offset), byte },
+            DecodeError::InvalidLength { length } => DecodeError::InvalidLength { length: length + symbols },
+            DecodeError::InvalidPadding { offset } => DecodeError::InvalidPadding { offset: position(offset) },
+            DecodeError::InvalidTrailingBits { offset } => DecodeError::InvalidTrailingBits { offset: position(offset) },
+            e @ (DecodeError::InvalidChecksum | DecodeError::Overflow) => e,
+        }
+    }
+}
+
+/// Base64 解码为任意字节，可省略末尾的填充
+///
+/// Example:
+/// ```rust
+/// use light_tool::base64::{self, DecodeError};
+/// assert_eq!(base64::decode_bytes("iVBORw==").unwrap(), [0x89, b'P', b'N', b'G']);
+/// assert_eq!(base64::decode_bytes("iVB*Rw=="), Err(DecodeError::InvalidByte { offset: 3, byte: b'*' }))
+/// ```
+pub fn decode_bytes(input: &str) -> Result<Vec<u8>, DecodeError> {
+    STANDARD.decode(input)
+}
+
+/// URL 安全的 Base64 解码，可省略末尾的填充
+///
+/// Example:
+/// ```rust
+/// use light_tool::base64;
+/// assert_eq!(base64::url_decode("-_8").unwrap(), [0xfb, 0xff])
+/// ```
+pub fn url_decode(input: &str) -> Result<Vec<u8>, DecodeError> {
+    URL_SAFE.decode(input)
+}
+
+/// MIME (RFC 2045) 规定的每行最大字符数
+pub const MIME_LINE_WIDTH: usize = 76;
+
+/// 流式 Base64 编码，写入的字节编码后写入内部的 `W`
+///
+/// 必须调用 `finish` 写出末尾不足 3 字节的部分及填充；未调用时在 drop 时尽力写出并忽略错误
+///
+/// Example:
+/// ```rust
+/// use std::io::Write;
+/// use light_tool::base64::{self, Base64Writer};
+/// let mut writer = Base64Writer::new(Vec::new(), base64::STANDARD);
+/// writer.write_all(b"Hello, ").unwrap();
+/// writer.write_all(b"World!").unwrap();
+/// assert_eq!(writer.finish().unwrap(), b"SGVsbG8sIFdvcmxkIQ==")
+/// ```
+pub struct Base64Writer<W: Write> {
+    inner: Option<W>,
+    engine: Engine,
+    line_width: usize,
+    column: usize,
+    pending: Vec<u8>,
+}
+
+impl<W: Write> Base64Writer<W> {
+    pub fn new(inner: W, engine: Engine) -> Self {
+        Self::with_line_wrap(inner, engine, 0)
+    }
+
+    /// 每 `line_width` 个字符插入 `\r\n`，为 0 时不换行；MIME 使用 `MIME_LINE_WIDTH`
+    pub fn with_line_wrap(inner: W, engine: Engine, line_width: usize) -> Self {
+        Base64Writer { inner: Some(inner), engine, line_width, column: 0, pending: Vec::with_capacity(3) }
+    }
+
+    /// 写出剩余数据及填充，返回内部的 `W`
+    pub fn finish(mut self) -> io::Result<W> {
+        self.write_pending()?;
+        Ok(self.inner.take().unwrap())
+    }
+
+    fn write_pending(&mut self) -> io::Result<()> {
+        if !self.pending.is_empty() {
+            let encoded = self.engine.encode(&self.pending);
+            self.pending.clear();
+            self.write_encoded(encoded.as_bytes())?;
+        }
+        Ok(())
+    }
+
+    fn write_encoded(&mut self, mut encoded: &[u8]) -> io::Result<()> {
+        let inner = self.inner.as_mut().unwrap();
+        if self.line_width == 0 {
+            return inner.write_all(encoded);
+        }
+        while !encoded.is_empty() {
+            if self.column == self.line_width {
+                inner.write_all(b"\r\n")?;
+                self.column = 0;
+            }
+            let n = encoded.len().min(self.line_width - self.column);
+            inner.write_all(&encoded[..n])?;
+            self.column += n;
+            encoded = &encoded[n..];
+        }
+        Ok(())
+    }
+}
+
+impl<W: Write> Write for Base64Writer<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        // 每次最多处理 3 KiB，避免一次性复制大块输入
+        let buf = &buf[..buf.len().min(3 * 1024)];
+        self.pending.extend_from_slice(buf);
+        let whole = self.pending.len() / 3 * 3;
+        if whole > 0 {
+            let encoded = self.engine.encode(&self.pending[..whole]);
+            self.pending.drain(..whole);
+            self.write_encoded(encoded.as_bytes())?;
+        }
+        Ok(buf.len())
+    }
+
+    /// 只刷新内部的 `W`，不足 3 字节的部分要等到 `finish` 才会写出
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.as_mut().unwrap().flush()
+    }
+}
+
+impl<W: Write> Drop for Base64Writer<W> {
+    fn drop(&mut self) {
+        if self.inner.is_some() {
+            let _ = self.write_pending();
+        }
+    }
+}
+
+/// 流式 Base64 解码，从内部的 `R` 读取编码后的文本并输出解码后的字节
+///
+/// 解码错误以 `io::ErrorKind::InvalidData` 返回，内部错误为带全局偏移的 `DecodeError`
+///
+/// Example:
+/// ```rust
+/// use std::io::Read;
+/// use light_tool::base64::{self, Base64Reader, DecodeMode};
+/// let mut reader = Base64Reader::new("SGVsbG8s\r\nIFdvcmxkIQ==".as_bytes(), base64::STANDARD, DecodeMode::Lenient);
+/// let mut output = String::new();
+/// reader.read_to_string(&mut output).unwrap();
+/// assert_eq!(output, "Hello, World!")
+/// ```
+pub struct Base64Reader<R: Read> {
+    inner: R,
+    engine: Engine,
+    mode: DecodeMode,
+    /// 尚未解码的字符，不含宽松模式下跳过的空白字符
+    raw: Vec<u8>,
+    /// `raw` 中每个字符在整个输入中的偏移
+    offsets: Vec<usize>,
+    /// 已读取的输入字节数
+    read: usize,
+    /// 已解码的有效字符数（不含填充）
+    symbols: usize,
+    decoded: Vec<u8>,
+    pos: usize,
+    /// 已解码的部分包含填充
+    padded: bool,
+    eof: bool,
+}
+
+impl<R: Read> Base64Reader<R> {
+    pub fn new(inner: R, engine: Engine, mode: DecodeMode) -> Self {
+        Base64Reader {
+            inner,
+            engine,
+            mode,
+            raw: Vec::new(),
+            offsets: Vec::new(),
+            read: 0,
+            symbols: 0,
+            decoded: Vec::new(),
+            pos: 0,
+            padded: false,
+            eof: false,
+        }
+    }
+
+    /// 取回内部的 `R`
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn skip(&self, byte: u8) -> bool {
+        self.mode == DecodeMode::Lenient && byte.is_ascii_whitespace()
+    }
+
+    fn fill(&mut self) -> io::Result<()> {
+        let mut chunk = [0u8; 4096];
+        let n = self.inner.read(&mut chunk)?;
+        if n == 0 {
+            self.eof = true;
+            return self.decode_segment(self.raw.len());
+        }
+        for (i, &byte) in chunk[..n].iter().enumerate() {
+            if !self.skip(byte) {
+                self.raw.push(byte);
+                self.offsets.push(self.read + i);
+            }
+        }
+        self.read += n;
+
+        // 只解码完整的 4 字符分组，剩余部分等待更多输入
+        self.decode_segment(self.raw.len() / 4 * 4)
+    }
+
+    fn decode_segment(&mut self, end: usize) -> io::Result<()> {
+        let segment = &self.raw[..end];
+        // 超出本段的偏移（如末尾缺少填充）对应输入末尾
+        let position = |i: usize| self.offsets.get(i).copied().unwrap_or(self.read);
+        let error = |e: DecodeError| io::Error::new(io::ErrorKind::InvalidData, e);
+        if let Some(i) = segment.iter().position(|&b| !b.is_ascii() || self.padded) {
+            let offset = position(i);
+            return Err(error(match segment[i] {
+                byte if !byte.is_ascii() => DecodeError::InvalidByte { offset, byte },
+                _ => DecodeError::InvalidPadding { offset },
+            }));
+        }
+
+        let text = std::str::from_utf8(segment).unwrap();
+        self.decoded = self
+            .engine
+            .decode_with(text, self.mode)
+            .map_err(|e| error(e.relocate(position, self.symbols)))?;
+        self.pos = 0;
+        self.padded |= segment.contains(&b'=');
+        self.symbols += segment.iter().filter(|&&b| b != b'=').count();
+        self.raw.drain(..end);
+        self.offsets.drain(..end);
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for Base64Reader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.pos == self.decoded.len() {
+            if self.eof {
+                return Ok(0);
+            }
+            self.fill()?;
+        }
+        let n = buf.len().min(self.decoded.len() - self.pos);
+        buf[..n].copy_from_slice(&self.decoded[self.pos..self.pos + n]);
+        self.pos += n;
+        Ok(n)
+    }
 }
 
 #[cfg(test)]
@@ -95,4 +570,120 @@ mod tests {
     fn test_decode() {
         assert_eq!(decode("SGVsbG8sIFdvcmxkIQ==").unwrap(), "Hello, World!")
     }
+
+    #[test]
+    fn test_url() {
+        assert_eq!(url_encode("Hello, World!"), "SGVsbG8sIFdvcmxkIQ");
+        assert_eq!(url_encode([0xfb, 0xef, 0xff]), "--__");
+        assert_eq!(url_decode("--__").unwrap(), [0xfb, 0xef, 0xff]);
+        assert_eq!(url_decode("SGVsbG8sIFdvcmxkIQ==").unwrap(), b"Hello, World!");
+        assert!(url_decode("++//").is_err());
+    }
+
+    #[test]
+    fn test_bytes() {
+        let bytes: Vec<u8> = (0..=255).collect();
+        assert_eq!(decode_bytes(&encode_bytes(&bytes)).unwrap(), bytes);
+        assert_eq!(decode_bytes("/w").unwrap(), [0xff]);
+        assert_eq!(decode_bytes("SGVsbG8-"), Err(DecodeError::InvalidByte { offset: 7, byte: b'-' }));
+        assert_eq!(decode_bytes("SGVsb"), Err(DecodeError::InvalidLength { length: 5 }));
+        assert_eq!(decode_bytes("SGk=SGk="), Err(DecodeError::InvalidPadding { offset: 4 }));
+        assert!(decode("/w==").is_err());
+    }
+
+    #[test]
+    fn test_engine() {
+        assert_eq!(BCRYPT.encode("Hello, World!"), "QETqZE6qGDbtakviGO");
+        assert_eq!(CRYPT.encode("Hello, World!"), "G4JgP4wg63RjQalY6E");
+        assert_eq!(BCRYPT.decode("QETqZE6qGDbtakviGO").unwrap(), b"Hello, World!");
+        assert_eq!(STANDARD_NO_PAD.encode("Hi"), "SGk");
+
+        let required = Engine::new(Alphabet::STANDARD, Padding::Required);
+        assert_eq!(required.decode("SGk=").unwrap(), b"Hi");
+        assert_eq!(required.decode("SGk"), Err(DecodeError::InvalidPadding { offset: 3 }));
+        assert_eq!(required.decode("SGk=="), Err(DecodeError::InvalidPadding { offset: 3 }));
+        assert_eq!(STANDARD.decode("SGk").unwrap(), b"Hi");
+        assert_eq!(STANDARD.decode("SGk=="), Err(DecodeError::InvalidPadding { offset: 3 }));
+        assert_eq!(STANDARD_NO_PAD.decode("SGk="), Err(DecodeError::InvalidPadding { offset: 3 }));
+
+        assert!(Alphabet::new(&"A".repeat(64)).is_err());
+        assert!(Alphabet::new("ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+=").is_err());
+    }
+
+    #[test]
+    fn test_decode_mode() {
+        let mime = "SGVsbG8s\r\nIFdvcmxk\r\nIQ==\r\n";
+        assert_eq!(STANDARD.decode_with(mime, DecodeMode::Lenient).unwrap(), b"Hello, World!");
+        assert_eq!(STANDARD.decode_with(" S G k = ", DecodeMode::Lenient).unwrap(), b"Hi");
+        assert_eq!(STANDARD_NO_PAD.decode_with("SGk=\n", DecodeMode::Lenient).unwrap(), b"Hi");
+        assert_eq!(STANDARD.decode(mime), Err(DecodeError::InvalidByte { offset: 8, byte: b'\r' }));
+        assert_eq!(STANDARD.decode_with(mime, DecodeMode::Strict), Err(DecodeError::InvalidByte { offset: 8, byte: b'\r' }));
+
+        assert_eq!(STANDARD.decode_with("SGk=", DecodeMode::Strict).unwrap(), b"Hi");
+        assert_eq!(STANDARD.decode_with("SGl=", DecodeMode::Standard).unwrap(), b"Hi");
+        assert_eq!(STANDARD.decode_with("SGl=", DecodeMode::Strict), Err(DecodeError::InvalidTrailingBits { offset: 2 }));
+        assert_eq!(STANDARD.decode_with("/x==", DecodeMode::Strict), Err(DecodeError::InvalidTrailingBits { offset: 1 }));
+        assert_eq!(STANDARD.decode_with("SGk", DecodeMode::Strict), Err(DecodeError::InvalidPadding { offset: 3 }));
+        assert_eq!(STANDARD_NO_PAD.decode_with("SGk", DecodeMode::Strict).unwrap(), b"Hi");
+        assert_eq!(STANDARD_NO_PAD.decode_with("SGk=", DecodeMode::Strict), Err(DecodeError::InvalidPadding { offset: 3 }));
+    }
+
+    #[test]
+    fn test_stream() {
+        let data: Vec<u8> = (0..10000u32).map(|i| (i * 7 % 251) as u8).collect();
+
+        // 逐字节写入，结果应与一次性编码相同
+        let mut writer = Base64Writer::new(Vec::new(), STANDARD);
+        for byte in &data {
+            writer.write_all(&[*byte]).unwrap();
+        }
+        let encoded = writer.finish().unwrap();
+        assert_eq!(encoded, encode_bytes(&data).as_bytes());
+
+        let mut writer = Base64Writer::with_line_wrap(Vec::new(), STANDARD, MIME_LINE_WIDTH);
+        writer.write_all(&data).unwrap();
+        let wrapped = String::from_utf8(writer.finish().unwrap()).unwrap();
+        assert!(wrapped.split("\r\n").all(|line| line.len() <= MIME_LINE_WIDTH));
+        assert_eq!(wrapped.split("\r\n").next().unwrap().len(), MIME_LINE_WIDTH);
+        assert!(!wrapped.ends_with('\n'));
+
+        let mut decoded = Vec::new();
+        Base64Reader::new(wrapped.as_bytes(), STANDARD, DecodeMode::Lenient).read_to_end(&mut decoded).unwrap();
+        assert_eq!(decoded, data);
+
+        // 小块读取
+        let mut reader = Base64Reader::new(&encoded[..], STANDARD, DecodeMode::Strict);
+        let mut decoded = Vec::new();
+        let mut buf = [0u8; 7];
+        loop {
+            let n = reader.read(&mut buf).unwrap();
+            if n == 0 {
+                break;
+            }
+            decoded.extend_from_slice(&buf[..n]);
+        }
+        assert_eq!(decoded, data);
+
+        // 错误偏移为整个输入中的位置
+        let mut bad = encoded.clone();
+        bad[5000] = b'*';
+        let error = Base64Reader::new(&bad[..], STANDARD, DecodeMode::Standard).read_to_end(&mut Vec::new()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(error.into_inner().unwrap().downcast::<DecodeError>().unwrap().as_ref(), &DecodeError::InvalidByte { offset: 5000, byte: b'*' });
+
+        let error = Base64Reader::new("SGk=SGk=".as_bytes(), STANDARD, DecodeMode::Standard).read_to_end(&mut Vec::new()).unwrap_err();
+        assert_eq!(error.into_inner().unwrap().downcast::<DecodeError>().unwrap().as_ref(), &DecodeError::InvalidPadding { offset: 4 });
+
+        // 长度错误与 decode_bytes 一致，报告整个输入中的有效字符数
+        let error = Base64Reader::new("SGVsb".as_bytes(), STANDARD, DecodeMode::Standard).read_to_end(&mut Vec::new()).unwrap_err();
+        assert_eq!(error.into_inner().unwrap().downcast::<DecodeError>().unwrap().as_ref(), &DecodeError::InvalidLength { length: 5 });
+        let error = Base64Reader::new("SGVs\nbG8s\nb".as_bytes(), STANDARD, DecodeMode::Lenient).read_to_end(&mut Vec::new()).unwrap_err();
+        assert_eq!(error.into_inner().unwrap().downcast::<DecodeError>().unwrap().as_ref(), &DecodeError::InvalidLength { length: 9 });
+        assert_eq!(decode_bytes("SGVsbG8sb"), Err(DecodeError::InvalidLength { length: 9 }));
+
+        // 宽松模式下跳过的空白字符不会积压在缓冲区中
+        let mut reader = Base64Reader::new(io::repeat(b' ').take(1 << 20), STANDARD, DecodeMode::Lenient);
+        assert_eq!(reader.read(&mut [0u8; 16]).unwrap(), 0);
+        assert!(reader.raw.capacity() < 4096);
+    }
 }
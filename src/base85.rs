@@ -0,0 +1,201 @@
+pub use crate::base64::DecodeError;
+
+/// Z85 编码表 (ZeroMQ RFC 32)，不含引号与反斜杠，可直接嵌入字符串与配置文件
+const Z85_CHARSET: &[u8; 85] = b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ.-:+=^!/*?&<>()[]{}@%$#";
+
+/// Ascii85 编码表，`!` 到 `u`
+const ASCII85_CHARSET: [u8; 85] = ascii85_charset();
+
+const Z85_VALUES: [u8; 256] = values(Z85_CHARSET);
+const ASCII85_VALUES: [u8; 256] = values(&ASCII85_CHARSET);
+
+const fn ascii85_charset() -> [u8; 85] {
+    let mut charset = [0u8; 85];
+    let mut i = 0;
+    while i < 85 {
+        charset[i] = b'!' + i as u8;
+        i += 1;
+    }
+    charset
+}
+
+/// 字符到值的反查表，0xff 表示非法字符
+const fn values(charset: &[u8; 85]) -> [u8; 256] {
+    let mut table = [0xff; 256];
+    let mut i = 0;
+    while i < 85 {
+        table[charset[i] as usize] = i as u8;
+        i += 1;
+    }
+    table
+}
+
+/// Z85 编码，每 4 字节编码为 5 个字符
+///
+/// 长度为 4 的倍数时与 ZeroMQ 规范完全一致；否则末尾不足 4 字节的部分编码为 n + 1 个字符
+///
+/// Example:
+/// ```rust
+/// use light_tool::base85;
+/// assert_eq!(base85::z85_encode([0x86, 0x4f, 0xd2, 0x6f, 0xb5, 0x59, 0xf7, 0x5b]), "HelloWorld")
+/// ```
+pub fn z85_encode<T: AsRef<[u8]>>(input: T) -> String {
+    encode_with(input.as_ref(), Z85_CHARSET, false)
+}
+
+/// Z85 解码
+///
+/// Example:
+/// ```rust
+/// use light_tool::base85::{self, DecodeError};
+/// assert_eq!(base85::z85_decode("HelloWorld").unwrap(), [0x86, 0x4f, 0xd2, 0x6f, 0xb5, 0x59, 0xf7, 0x5b]);
+/// assert_eq!(base85::z85_decode("Hello World"), Err(DecodeError::InvalidByte { offset: 5, byte: b' ' }))
+/// ```
+pub fn z85_decode(input: &str) -> Result<Vec<u8>, DecodeError> {
+    decode_with(input.bytes().enumerate(), &Z85_VALUES, false)
+}
+
+/// Ascii85 编码 (btoa / PDF)，全零的 4 字节组缩写为 `z`，不带 `<~ ~>` 定界符
+///
+/// Example:
+/// ```rust
+/// use light_tool::base85;
+/// assert_eq!(base85::ascii85_encode("Man is distinguished"), "9jqo^BlbD-BleB1DJ+*+F(f,q")
+/// ```
+pub fn ascii85_encode<T: AsRef<[u8]>>(input: T) -> String {
+    encode_with(input.as_ref(), &ASCII85_CHARSET, true)
+}
+
+/// Ascii85 解码，忽略空白字符，可带 Adobe 风格的 `<~ ~>` 定界符
+///
+/// Example:
+/// ```rust
+/// use light_tool::base85;
+/// assert_eq!(base85::ascii85_decode("<~9jqo^BlbD-\nBleB1DJ+*+F(f,q~>").unwrap(), b"Man is distinguished");
+/// assert_eq!(base85::ascii85_decode("z@:E^").unwrap(), b"\0\0\0\0abc")
+/// ```
+pub fn ascii85_decode(input: &str) -> Result<Vec<u8>, DecodeError> {
+    let bytes = input.as_bytes();
+    let start = bytes.iter().position(|b| !b.is_ascii_whitespace()).unwrap_or(bytes.len());
+    let end = bytes.iter().rposition(|b| !b.is_ascii_whitespace()).map_or(start, |i| i + 1);
+    let mut data = start..end;
+    if bytes[data.clone()].starts_with(b"<~") {
+        data.start += 2;
+    }
+    if bytes[data.clone()].ends_with(b"~>") {
+        data.end -= 2;
+    }
+
+    let symbols = bytes[data.clone()].iter().enumerate().map(|(i, &b)| (data.start + i, b));
+    decode_with(symbols.filter(|(_, b)| !b.is_ascii_whitespace()), &ASCII85_VALUES, true)
+}
+
+fn encode_with(bytes: &[u8], charset: &[u8; 85], zero_group: bool) -> String {
+    let mut output = String::with_capacity(bytes.len().div_ceil(4) * 5);
+    for chunk in bytes.chunks(4) {
+        let mut group = [0u8; 4];
+        group[..chunk.len()].copy_from_slice(chunk);
+        let mut value = u32::from_be_bytes(group);
+        if zero_group && chunk.len() == 4 && value == 0 {
+            output.push('z');
+            continue;
+        }
+
+        let mut symbols = [0u8; 5];
+        for symbol in symbols.iter_mut().rev() {
+            *symbol = charset[(value % 85) as usize];
+            value /= 85;
+        }
+        output.extend(symbols[..chunk.len() + 1].iter().map(|&s| s as char));
+    }
+    output
+}
+
+/// 按 (偏移, 字符) 逐组解码，偏移用于错误定位；末尾不足 5 个字符的组以最大值补齐
+fn decode_with<I>(symbols: I, values: &[u8; 256], zero_group: bool) -> Result<Vec<u8>, DecodeError>
+where
+    I: Iterator<Item = (usize, u8)>,
+{
+    let mut output = Vec::new();
+    let mut group = [0u8; 5];
+    let mut len = 0;
+    let mut count = 0;
+    // 当前组首字符，数值溢出时作为出错位置
+    let mut first = (0, 0);
+    for (offset, byte) in symbols {
+        count += 1;
+        if zero_group && byte == b'z' {
+            if len != 0 {
+                return Err(DecodeError::InvalidByte { offset, byte });
+            }
+            output.extend_from_slice(&[0; 4]);
+            continue;
+        }
+        let value = values[byte as usize];
+        if value == 0xff {
+            return Err(DecodeError::InvalidByte { offset, byte });
+        }
+        if len == 0 {
+            first = (offset, byte);
+        }
+        group[len] = value;
+        len += 1;
+        if len == 5 {
+            push_group(&mut output, &group, 4, first)?;
+            len = 0;
+        }
+    }
+
+    match len {
+        0 => {}
+        1 => return Err(DecodeError::InvalidLength { length: count }),
+        _ => {
+            group[len..].fill(84);
+            push_group(&mut output, &group, len - 1, first)?;
+        }
+    }
+    Ok(output)
+}
+
+fn push_group(output: &mut Vec<u8>, group: &[u8; 5], len: usize, (offset, byte): (usize, u8)) -> Result<(), DecodeError> {
+    let value = group
+        .iter()
+        .try_fold(0u32, |acc, &digit| acc.checked_mul(85)?.checked_add(digit as u32))
+        .ok_or(DecodeError::InvalidByte { offset, byte })?;
+    output.extend_from_slice(&value.to_be_bytes()[..len]);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_z85() {
+        let bytes = [0x86, 0x4f, 0xd2, 0x6f, 0xb5, 0x59, 0xf7, 0x5b];
+        assert_eq!(z85_encode(bytes), "HelloWorld");
+        assert_eq!(z85_decode("HelloWorld").unwrap(), bytes);
+        for len in 0..=9usize {
+            let data: Vec<u8> = (0..len).map(|i| 0xff - i as u8).collect();
+            let encoded = z85_encode(&data);
+            assert_eq!(encoded.len(), len + len.div_ceil(4));
+            assert_eq!(z85_decode(&encoded).unwrap(), data);
+        }
+        assert_eq!(z85_decode("Hello0"), Err(DecodeError::InvalidLength { length: 6 }));
+        assert_eq!(z85_decode("#0000"), Err(DecodeError::InvalidByte { offset: 0, byte: b'#' }));
+    }
+
+    #[test]
+    fn test_ascii85() {
+        assert_eq!(ascii85_encode("hello world"), "BOu!rD]j7BEbo7");
+        assert_eq!(ascii85_decode("BOu!rD]j7BEbo7").unwrap(), b"hello world");
+        assert_eq!(ascii85_encode(b"\0\0\0\0abc"), "z@:E^");
+        assert_eq!(ascii85_decode(" <~ z @:E^ ~> ").unwrap(), b"\0\0\0\0abc");
+        assert_eq!(ascii85_encode(""), "");
+        assert_eq!(ascii85_decode("<~~>").unwrap(), b"");
+        assert_eq!(ascii85_decode("BOzu"), Err(DecodeError::InvalidByte { offset: 2, byte: b'z' }));
+        assert_eq!(ascii85_decode("BOu!rD"), Err(DecodeError::InvalidLength { length: 6 }));
+        assert_eq!(ascii85_decode("s8W-\""), Err(DecodeError::InvalidByte { offset: 0, byte: b's' }));
+        assert_eq!(ascii85_decode("BOu!v"), Err(DecodeError::InvalidByte { offset: 4, byte: b'v' }));
+    }
+}
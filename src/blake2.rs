@@ -0,0 +1,322 @@
+use crate::digest::Digest;
+
+/// BLAKE2 消息字排列
+const SIGMA: [[usize; 16]; 10] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+];
+
+/// BLAKE2b 初始值（同 SHA-512）
+const IV_B: [u64; 8] = [
+    0x6a09e667f3bcc908, 0xbb67ae8584caa73b, 0x3c6ef372fe94f82b, 0xa54ff53a5f1d36f1,
+    0x510e527fade682d1, 0x9b05688c2b3e6c1f, 0x1f83d9abfb41bd6b, 0x5be0cd19137e2179,
+];
+
+/// BLAKE2s 初始值（同 SHA-256）
+const IV_S: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+macro_rules! blake2 {
+    (
+        $name:ident, $word:ty, $iv:expr, $block:expr, $max_out:expr, $rounds:expr,
+        ($r1:expr, $r2:expr, $r3:expr, $r4:expr), $doc:expr
+    ) => {
+        #[doc = $doc]
+        #[derive(Clone)]
+        pub struct $name {
+            state: [$word; 8],           // 哈希状态
+            buffer: [u8; $block],        // 最后一个分组需在结束时处理，因此缓冲区可存满一个分组
+            buffer_len: usize,           // 缓冲区中的字节数
+            counter: u128,               // 已处理的字节数
+            out_len: usize,              // 输出长度（字节）
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl $name {
+            /// 初始化哈希状态，输出最大长度
+            pub fn new() -> Self {
+                Self::with_params($max_out, &[], &[], &[])
+            }
+
+            /// 带密钥的 MAC 模式
+            pub fn new_keyed(key: &[u8], out_len: usize) -> Self {
+                Self::with_params(out_len, key, &[], &[])
+            }
+
+            /// 指定输出长度、密钥、盐值与个性化字符串，参数不合法时 panic，见 `try_with_params`
+            pub fn with_params(out_len: usize, key: &[u8], salt: &[u8], personal: &[u8]) -> Self {
+                Self::try_with_params(out_len, key, salt, personal).unwrap_or_else(|e| panic!("{}", e))
+            }
+
+            /// 指定输出长度、密钥、盐值与个性化字符串
+            ///
+            /// 输出长度不能为 0 且不能超过最大长度，密钥不能超过最大长度，
+            /// 盐值与个性化字符串不能超过参数块中的长度，不满足时返回错误
+            pub fn try_with_params(out_len: usize, key: &[u8], salt: &[u8], personal: &[u8]) -> Result<Self, String> {
+                const WORD: usize = std::mem::size_of::<$word>();
+                if !(1..=$max_out).contains(&out_len) {
+                    return Err(format!("invalid output length: {}", out_len));
+                }
+                if key.len() > $max_out {
+                    return Err(format!("key too long: {}", key.len()));
+                }
+                if salt.len() > WORD * 2 {
+                    return Err(format!("salt too long: {}", salt.len()));
+                }
+                if personal.len() > WORD * 2 {
+                    return Err(format!("personalization too long: {}", personal.len()));
+                }
+
+                // 参数块：输出长度、密钥长度、fanout = 1、depth = 1，其余为 0
+                let mut state = $iv;
+                state[0] ^= 0x01010000 ^ ((key.len() as $word) << 8) ^ (out_len as $word);
+                let mut param = [0u8; WORD * 4];
+                param[..salt.len()].copy_from_slice(salt);
+                param[WORD * 2..WORD * 2 + personal.len()].copy_from_slice(personal);
+                for (i, chunk) in param.chunks(WORD).enumerate() {
+                    state[4 + i] ^= <$word>::from_le_bytes(chunk.try_into().unwrap());
+                }
+
+                let mut hasher = Self {
+                    state,
+                    buffer: [0u8; $block],
+                    buffer_len: 0,
+                    counter: 0,
+                    out_len,
+                };
+                if !key.is_empty() {
+                    hasher.buffer[..key.len()].copy_from_slice(key);
+                    hasher.buffer_len = $block;
+                }
+                Ok(hasher)
+            }
+
+            /// 写入数据
+            pub fn update(&mut self, mut input: &[u8]) {
+                while !input.is_empty() {
+                    if self.buffer_len == $block {
+                        self.counter += $block as u128;
+                        let block = self.buffer;
+                        self.compress(&block, false);
+                        self.buffer_len = 0;
+                    }
+                    if self.buffer_len == 0 {
+                        while input.len() > $block {
+                            self.counter += $block as u128;
+                            self.compress(input[..$block].try_into().unwrap(), false);
+                            input = &input[$block..];
+                        }
+                    }
+                    let take = ($block - self.buffer_len).min(input.len());
+                    self.buffer[self.buffer_len..self.buffer_len + take].copy_from_slice(&input[..take]);
+                    self.buffer_len += take;
+                    input = &input[take..];
+                }
+            }
+
+            /// 计算最终的哈希值，长度为初始化时指定的输出长度
+            pub fn finalize(mut self) -> Vec<u8> {
+                self.counter += self.buffer_len as u128;
+                self.buffer[self.buffer_len..].fill(0);
+                let block = self.buffer;
+                self.compress(&block, true);
+
+                let mut digest: Vec<u8> = self.state.iter().flat_map(|word| word.to_le_bytes()).collect();
+                digest.truncate(self.out_len);
+                digest
+            }
+
+            fn compress(&mut self, block: &[u8; $block], last: bool) {
+                const WORD: usize = std::mem::size_of::<$word>();
+                let mut m = [0 as $word; 16];
+                for (i, chunk) in block.chunks(WORD).enumerate() {
+                    m[i] = <$word>::from_le_bytes(chunk.try_into().unwrap());
+                }
+
+                let mut v = [0 as $word; 16];
+                v[..8].copy_from_slice(&self.state);
+                v[8..].copy_from_slice(&$iv);
+                v[12] ^= self.counter as $word;
+                v[13] ^= (self.counter >> (WORD * 8)) as $word;
+                if last {
+                    v[14] = !v[14];
+                }
+
+                let g = |v: &mut [$word; 16], a: usize, b: usize, c: usize, d: usize, x: $word, y: $word| {
+                    v[a] = v[a].wrapping_add(v[b]).wrapping_add(x);
+                    v[d] = (v[d] ^ v[a]).rotate_right($r1);
+                    v[c] = v[c].wrapping_add(v[d]);
+                    v[b] = (v[b] ^ v[c]).rotate_right($r2);
+                    v[a] = v[a].wrapping_add(v[b]).wrapping_add(y);
+                    v[d] = (v[d] ^ v[a]).rotate_right($r3);
+                    v[c] = v[c].wrapping_add(v[d]);
+                    v[b] = (v[b] ^ v[c]).rotate_right($r4);
+                };
+
+                for round in 0..$rounds {
+                    let s = &SIGMA[round % 10];
+                    g(&mut v, 0, 4, 8, 12, m[s[0]], m[s[1]]);
+                    g(&mut v, 1, 5, 9, 13, m[s[2]], m[s[3]]);
+                    g(&mut v, 2, 6, 10, 14, m[s[4]], m[s[5]]);
+                    g(&mut v, 3, 7, 11, 15, m[s[6]], m[s[7]]);
+                    g(&mut v, 0, 5, 10, 15, m[s[8]], m[s[9]]);
+                    g(&mut v, 1, 6, 11, 12, m[s[10]], m[s[11]]);
+                    g(&mut v, 2, 7, 8, 13, m[s[12]], m[s[13]]);
+                    g(&mut v, 3, 4, 9, 14, m[s[14]], m[s[15]]);
+                }
+
+                for i in 0..8 {
+                    self.state[i] ^= v[i] ^ v[i + 8];
+                }
+            }
+        }
+
+        impl std::io::Write for $name {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.update(buf);
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+    };
+}
+
+blake2!(Blake2b, u64, IV_B, 128, 64, 12, (32, 24, 16, 63), "BLAKE2b 结构体，输出 1 ~ 64 字节");
+blake2!(Blake2s, u32, IV_S, 64, 32, 10, (16, 12, 8, 7), "BLAKE2s 结构体，输出 1 ~ 32 字节");
+
+macro_rules! blake2_fixed {
+    ($name:ident, $inner:ident, $output:expr, $block:expr, $doc:expr) => {
+        #[doc = $doc]
+        #[derive(Clone, Default)]
+        pub struct $name {
+            inner: $inner,
+        }
+
+        impl $name {
+            /// 初始化哈希状态
+            pub fn new() -> Self {
+                Self { inner: $inner::new() }
+            }
+
+            /// 写入数据
+            pub fn update(&mut self, input: &[u8]) {
+                self.inner.update(input);
+            }
+
+            /// 计算最终的哈希值
+            pub fn finalize(self) -> [u8; $output] {
+                self.inner.finalize().try_into().unwrap()
+            }
+        }
+
+        crate::digest::impl_digest!($name, $output, $block);
+    };
+}
+
+blake2_fixed!(Blake2b512, Blake2b, 64, 128, "输出 512 位的 BLAKE2b，实现了 `Digest`");
+blake2_fixed!(Blake2s256, Blake2s, 32, 64, "输出 256 位的 BLAKE2s，实现了 `Digest`");
+
+/// BLAKE2b-512 哈希函数
+///
+/// # Example
+/// ```rust
+/// use light_tool::blake2;
+/// println!("blake2b: {}", blake2::blake2b("Hello, world!"))
+/// ```
+pub fn blake2b<T>(input: T) -> String
+where
+    T: AsRef<[u8]>,
+{
+    Blake2b512::hex_digest(input)
+}
+
+/// BLAKE2s-256 哈希函数
+///
+/// # Example
+/// ```rust
+/// use light_tool::blake2;
+/// println!("blake2s: {}", blake2::blake2s("Hello, world!"))
+/// ```
+pub fn blake2s<T>(input: T) -> String
+where
+    T: AsRef<[u8]>,
+{
+    Blake2s256::hex_digest(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hex::encode as hex;
+
+    #[test]
+    fn test_blake2b() {
+        assert_eq!(
+            blake2b(""),
+            "786a02f742015903c6c6fd852552d272912f4740e15847618a86e217f71f5419d25e1031afee585313896444934eb04b903a685b1448b755d56f701afe9be2ce"
+        );
+        assert_eq!(
+            blake2b("abc"),
+            "ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d17d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923"
+        );
+
+        let mut hasher = Blake2b::with_params(32, b"secret key", b"salt", b"personal");
+        hasher.update(b"Hello, world!");
+        assert_eq!(hex(hasher.finalize()), "db777a1c8d55bfc42de564125f23ba232eb4d8d82378fa2c20612328c6d9d2ea");
+
+        assert!(Blake2b::try_with_params(64, &[0; 64], &[0; 16], &[0; 16]).is_ok());
+        assert!(Blake2b::try_with_params(0, &[], &[], &[]).is_err());
+        assert!(Blake2b::try_with_params(65, &[], &[], &[]).is_err());
+        assert!(Blake2b::try_with_params(32, &[0; 65], &[], &[]).is_err());
+        assert!(Blake2b::try_with_params(32, &[], &[0; 17], &[]).is_err());
+        assert!(Blake2b::try_with_params(32, &[], &[], &[0; 17]).is_err());
+    }
+
+    #[test]
+    fn test_blake2s() {
+        assert_eq!(blake2s(""), "69217a3079908094e11121d042354a7c1f55b6482ca1a51e1b250dfd1ed0eef9");
+        assert_eq!(blake2s("abc"), "508c5e8c327c14e2e1a72ba34eeb452f37458b209ed63a294d999b4c86675982");
+
+        let mut hasher = Blake2s::with_params(16, b"secret key", b"salt", b"personal");
+        hasher.update(b"Hello, world!");
+        assert_eq!(hex(hasher.finalize()), "45150326c89b62c547481a9af68a68c0");
+
+        assert!(Blake2s::try_with_params(32, &[0; 32], &[0; 8], &[0; 8]).is_ok());
+        assert!(Blake2s::try_with_params(33, &[], &[], &[]).is_err());
+        assert!(Blake2s::try_with_params(16, &[0; 33], &[], &[]).is_err());
+        assert!(Blake2s::try_with_params(16, &[], &[0; 9], &[]).is_err());
+    }
+
+    #[test]
+    fn test_blake2_keyed_streaming() {
+        crate::digest::assert_streaming::<Blake2b512>();
+        crate::digest::assert_streaming::<Blake2s256>();
+
+        // 密钥独占第一个分组，最后一个分组要到结束时才压缩
+        let key: Vec<u8> = (0..64u8).collect();
+        let data = crate::digest::test_data(1000);
+        for len in [0, 127, 128, 129, 1000] {
+            crate::digest::assert_chunked(|| Blake2b::new_keyed(&key, 64), Blake2b::update, Blake2b::finalize, &data[..len], &[1, 127, 128, 129]);
+        }
+        let mut hasher = Blake2b::new_keyed(&key, 64);
+        hasher.update(&data);
+        assert_eq!(hex(hasher.finalize()), "715377e0611515b904d259ce52fc8e5d2c50468b1680b2984786b6949cc571f453d28cfb6969cb523ec84e06bf2a4465f3f37511db7792228d038942935750c1");
+    }
+}
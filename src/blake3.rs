@@ -0,0 +1,492 @@
+use std::thread;
+use crate::hex;
+
+const OUT_LEN: usize = 32;
+const BLOCK_LEN: usize = 64;
+const CHUNK_LEN: usize = 1024;
+
+// 域分隔标志
+const CHUNK_START: u32 = 1 << 0;
+const CHUNK_END: u32 = 1 << 1;
+const PARENT: u32 = 1 << 2;
+const ROOT: u32 = 1 << 3;
+const KEYED_HASH: u32 = 1 << 4;
+const DERIVE_KEY_CONTEXT: u32 = 1 << 5;
+const DERIVE_KEY_MATERIAL: u32 = 1 << 6;
+
+/// 初始值（同 SHA-256）
+const IV: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+/// 每轮之间的消息字排列
+const MSG_PERMUTATION: [usize; 16] = [2, 6, 3, 10, 7, 0, 4, 13, 1, 11, 12, 5, 9, 14, 15, 8];
+
+fn g(state: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize, x: u32, y: u32) {
+    state[a] = state[a].wrapping_add(state[b]).wrapping_add(x);
+    state[d] = (state[d] ^ state[a]).rotate_right(16);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_right(12);
+    state[a] = state[a].wrapping_add(state[b]).wrapping_add(y);
+    state[d] = (state[d] ^ state[a]).rotate_right(8);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_right(7);
+}
+
+fn round(state: &mut [u32; 16], m: &[u32; 16]) {
+    g(state, 0, 4, 8, 12, m[0], m[1]);
+    g(state, 1, 5, 9, 13, m[2], m[3]);
+    g(state, 2, 6, 10, 14, m[4], m[5]);
+    g(state, 3, 7, 11, 15, m[6], m[7]);
+    g(state, 0, 5, 10, 15, m[8], m[9]);
+    g(state, 1, 6, 11, 12, m[10], m[11]);
+    g(state, 2, 7, 8, 13, m[12], m[13]);
+    g(state, 3, 4, 9, 14, m[14], m[15]);
+}
+
+/// 压缩函数
+fn compress(cv: &[u32; 8], block: &[u32; 16], counter: u64, block_len: u32, flags: u32) -> [u32; 16] {
+    let mut state = [
+        cv[0], cv[1], cv[2], cv[3], cv[4], cv[5], cv[6], cv[7],
+        IV[0], IV[1], IV[2], IV[3],
+        counter as u32, (counter >> 32) as u32, block_len, flags,
+    ];
+    let mut m = *block;
+    for i in 0..7 {
+        round(&mut state, &m);
+        if i < 6 {
+            m = MSG_PERMUTATION.map(|j| m[j]);
+        }
+    }
+    for i in 0..8 {
+        state[i] ^= state[i + 8];
+        state[i + 8] ^= cv[i];
+    }
+    state
+}
+
+fn words(bytes: &[u8]) -> [u32; 16] {
+    let mut words = [0u32; 16];
+    for (word, chunk) in words.iter_mut().zip(bytes.chunks(4)) {
+        *word = u32::from_le_bytes(chunk.try_into().unwrap());
+    }
+    words
+}
+
+fn first_8(words: [u32; 16]) -> [u32; 8] {
+    words[..8].try_into().unwrap()
+}
+
+/// 尚未决定是否为根节点的压缩输入
+#[derive(Clone)]
+struct Output {
+    cv: [u32; 8],
+    block: [u32; 16],
+    counter: u64,
+    block_len: u32,
+    flags: u32,
+}
+
+impl Output {
+    fn chaining_value(&self) -> [u32; 8] {
+        first_8(compress(&self.cv, &self.block, self.counter, self.block_len, self.flags))
+    }
+
+    /// 根节点输出的第 `counter` 个 64 字节分组
+    fn root_block(&self, counter: u64) -> [u8; BLOCK_LEN] {
+        let words = compress(&self.cv, &self.block, counter, self.block_len, self.flags | ROOT);
+        let mut bytes = [0u8; BLOCK_LEN];
+        for (chunk, word) in bytes.chunks_mut(4).zip(words) {
+            chunk.copy_from_slice(&word.to_le_bytes());
+        }
+        bytes
+    }
+}
+
+fn parent_output(left: &[u32; 8], right: &[u32; 8], key: &[u32; 8], flags: u32) -> Output {
+    let mut block = [0u32; 16];
+    block[..8].copy_from_slice(left);
+    block[8..].copy_from_slice(right);
+    Output { cv: *key, block, counter: 0, block_len: BLOCK_LEN as u32, flags: PARENT | flags }
+}
+
+/// 单个 1024 字节块的状态
+#[derive(Clone)]
+struct ChunkState {
+    cv: [u32; 8],
+    counter: u64,
+    block: [u8; BLOCK_LEN],
+    block_len: usize,
+    blocks_compressed: usize,
+    flags: u32,
+}
+
+impl ChunkState {
+    fn new(key: &[u32; 8], counter: u64, flags: u32) -> Self {
+        Self { cv: *key, counter, block: [0u8; BLOCK_LEN], block_len: 0, blocks_compressed: 0, flags }
+    }
+
+    fn len(&self) -> usize {
+        BLOCK_LEN * self.blocks_compressed + self.block_len
+    }
+
+    fn start_flag(&self) -> u32 {
+        if self.blocks_compressed == 0 { CHUNK_START } else { 0 }
+    }
+
+    fn update(&mut self, mut input: &[u8]) {
+        while !input.is_empty() {
+            // 最后一个分组需带 CHUNK_END 标志，因此只在有更多数据时压缩缓冲区
+            if self.block_len == BLOCK_LEN {
+                let flags = self.flags | self.start_flag();
+                self.cv = first_8(compress(&self.cv, &words(&self.block), self.counter, BLOCK_LEN as u32, flags));
+                self.blocks_compressed += 1;
+                self.block = [0u8; BLOCK_LEN];
+                self.block_len = 0;
+            }
+            let take = (BLOCK_LEN - self.block_len).min(input.len());
+            self.block[self.block_len..self.block_len + take].copy_from_slice(&input[..take]);
+            self.block_len += take;
+            input = &input[take..];
+        }
+    }
+
+    fn output(&self) -> Output {
+        Output {
+            cv: self.cv,
+            block: words(&self.block),
+            counter: self.counter,
+            block_len: self.block_len as u32,
+            flags: self.flags | self.start_flag() | CHUNK_END,
+        }
+    }
+}
+
+/// 计算一棵完整子树的链接值，`threads` 大于 1 时左右子树在不同线程中计算
+fn subtree_cv(input: &[u8], key: &[u32; 8], counter: u64, flags: u32, threads: usize) -> [u32; 8] {
+    if input.len() <= CHUNK_LEN {
+        let mut chunk = ChunkState::new(key, counter, flags);
+        chunk.update(input);
+        return chunk.output().chaining_value();
+    }
+
+    // 左子树取不超过输入长度的最大 2 的幂个块，且至少为右子树留下 1 字节
+    let left_len = ((input.len() - 1) / CHUNK_LEN + 1).next_power_of_two() / 2 * CHUNK_LEN;
+    let (left, right) = input.split_at(left_len);
+    let right_counter = counter + (left_len / CHUNK_LEN) as u64;
+
+    let (left_cv, right_cv) = if threads > 1 {
+        thread::scope(|s| {
+            let handle = s.spawn(|| subtree_cv(left, key, counter, flags, threads / 2));
+            let right_cv = subtree_cv(right, key, right_counter, flags, threads - threads / 2);
+            (handle.join().unwrap(), right_cv)
+        })
+    } else {
+        (subtree_cv(left, key, counter, flags, 1), subtree_cv(right, key, right_counter, flags, 1))
+    };
+    parent_output(&left_cv, &right_cv, key, flags).chaining_value()
+}
+
+/// 实际使用的线程数，不超过 CPU 核数与输入中的完整块数
+fn thread_limit(threads: usize, len: usize) -> usize {
+    let cores = thread::available_parallelism().map_or(1, |n| n.get());
+    threads.min(cores).min(len / CHUNK_LEN).max(1)
+}
+
+/// BLAKE3 结构体
+///
+/// # Example
+/// ```rust
+/// use light_tool::blake3::Blake3;
+/// let mut blake3 = Blake3::new();
+/// blake3.update(b"Hello, ");
+/// blake3.update(b"world!");
+/// println!("blake3: {:?}", blake3.finalize())
+/// ```
+#[derive(Clone)]
+pub struct Blake3 {
+    key: [u32; 8],
+    chunk: ChunkState,
+    cv_stack: Vec<[u32; 8]>,   // 已完成子树的链接值
+    flags: u32,
+}
+
+impl Default for Blake3 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Blake3 {
+    fn with_key_flags(key: [u32; 8], flags: u32) -> Self {
+        Self { key, chunk: ChunkState::new(&key, 0, flags), cv_stack: Vec::new(), flags }
+    }
+
+    /// 普通哈希模式
+    pub fn new() -> Self {
+        Self::with_key_flags(IV, 0)
+    }
+
+    /// 带 32 字节密钥的 MAC 模式
+    pub fn new_keyed(key: &[u8; 32]) -> Self {
+        Self::with_key_flags(first_8(words(key)), KEYED_HASH)
+    }
+
+    /// 密钥派生模式，`context` 应是全局唯一、硬编码的字符串
+    pub fn new_derive_key(context: &str) -> Self {
+        let mut context_hasher = Self::with_key_flags(IV, DERIVE_KEY_CONTEXT);
+        context_hasher.update(context.as_bytes());
+        let context_key = context_hasher.finalize();
+        Self::with_key_flags(first_8(words(&context_key)), DERIVE_KEY_MATERIAL)
+    }
+
+    /// 合并栈顶的子树，直到栈中子树数量与已处理块数的二进制 1 的个数一致
+    fn merge_cv_stack(&mut self, total_chunks: u64) {
+        while self.cv_stack.len() > total_chunks.count_ones() as usize {
+            let right = self.cv_stack.pop().unwrap();
+            let left = self.cv_stack.pop().unwrap();
+            self.cv_stack.push(parent_output(&left, &right, &self.key, self.flags).chaining_value());
+        }
+    }
+
+    fn push_cv(&mut self, cv: [u32; 8], counter: u64) {
+        self.merge_cv_stack(counter);
+        self.cv_stack.push(cv);
+    }
+
+    /// 写入数据
+    pub fn update(&mut self, input: &[u8]) {
+        self.update_parallel(input, 1);
+    }
+
+    /// 写入数据，按树形结构将完整子树分给 `threads` 个线程并行计算，线程数不超过 CPU 核数与完整块数
+    ///
+    /// # Example
+    /// ```rust
+    /// use light_tool::blake3::Blake3;
+    /// let data = vec![0u8; 1 << 20];
+    /// let mut blake3 = Blake3::new();
+    /// blake3.update_parallel(&data, 4);
+    /// println!("blake3: {:?}", blake3.finalize())
+    /// ```
+    pub fn update_parallel(&mut self, mut input: &[u8], threads: usize) {
+        let threads = thread_limit(threads, input.len());
+        // 先补满当前块
+        if self.chunk.len() > 0 {
+            let take = (CHUNK_LEN - self.chunk.len()).min(input.len());
+            self.chunk.update(&input[..take]);
+            input = &input[take..];
+            if input.is_empty() {
+                return;
+            }
+            let cv = self.chunk.output().chaining_value();
+            let counter = self.chunk.counter;
+            self.push_cv(cv, counter);
+            self.chunk = ChunkState::new(&self.key, counter + 1, self.flags);
+        }
+
+        // 按子树处理整块数据，至少留下 1 字节，保证最后的块不会被过早当成非根节点
+        while input.len() > CHUNK_LEN {
+            let counter = self.chunk.counter;
+            let mut subtree_len = ((input.len() - 1) / CHUNK_LEN + 1).next_power_of_two() / 2 * CHUNK_LEN;
+            // 子树的起始块序号必须是子树块数的整数倍
+            while counter % (subtree_len / CHUNK_LEN) as u64 != 0 {
+                subtree_len /= 2;
+            }
+            let cv = subtree_cv(&input[..subtree_len], &self.key, counter, self.flags, threads);
+            self.push_cv(cv, counter);
+            input = &input[subtree_len..];
+            self.chunk = ChunkState::new(&self.key, counter + (subtree_len / CHUNK_LEN) as u64, self.flags);
+        }
+
+        self.merge_cv_stack(self.chunk.counter);
+        self.chunk.update(input);
+    }
+
+    /// 根节点的输出
+    fn root_output(&self) -> Output {
+        let mut output = self.chunk.output();
+        for cv in self.cv_stack.iter().rev() {
+            output = parent_output(cv, &output.chaining_value(), &self.key, self.flags);
+        }
+        output
+    }
+
+    /// 计算最终的哈希值
+    pub fn finalize(self) -> [u8; OUT_LEN] {
+        self.root_output().root_block(0)[..OUT_LEN].try_into().unwrap()
+    }
+
+    /// 结束写入，返回可以读取任意长度输出的读取器
+    pub fn finalize_xof(self) -> OutputReader {
+        OutputReader { output: self.root_output(), position: 0 }
+    }
+}
+
+impl crate::digest::Digest for Blake3 {
+    type Output = [u8; OUT_LEN];
+
+    fn new() -> Self {
+        Blake3::new()
+    }
+
+    fn update(&mut self, input: &[u8]) {
+        Blake3::update(self, input)
+    }
+
+    fn finalize(self) -> Self::Output {
+        Blake3::finalize(self)
+    }
+
+    fn output_size() -> usize {
+        OUT_LEN
+    }
+
+    fn block_size() -> usize {
+        BLOCK_LEN
+    }
+}
+
+impl std::io::Write for Blake3 {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// BLAKE3 输出读取器，可多次读取，输出连续
+#[derive(Clone)]
+pub struct OutputReader {
+    output: Output,
+    position: u64,
+}
+
+impl OutputReader {
+    /// 读取下一段输出
+    pub fn read(&mut self, mut output: &mut [u8]) {
+        while !output.is_empty() {
+            let block = self.output.root_block(self.position / BLOCK_LEN as u64);
+            let offset = (self.position % BLOCK_LEN as u64) as usize;
+            let take = (BLOCK_LEN - offset).min(output.len());
+            output[..take].copy_from_slice(&block[offset..offset + take]);
+            output = &mut output[take..];
+            self.position += take as u64;
+        }
+    }
+}
+
+impl std::io::Read for OutputReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        OutputReader::read(self, buf);
+        Ok(buf.len())
+    }
+}
+
+/// BLAKE3 哈希函数
+///
+/// # Example
+/// ```rust
+/// use light_tool::blake3;
+/// println!("blake3: {}", blake3::blake3("Hello, world!"))
+/// ```
+pub fn blake3<T>(input: T) -> String
+where
+    T: AsRef<[u8]>,
+{
+    let mut hasher = Blake3::new();
+    hasher.update(input.as_ref());
+    hex::encode(hasher.finalize())
+}
+
+/// 使用多个线程计算 BLAKE3 哈希值，适合大文件等大块数据
+///
+/// # Example
+/// ```rust
+/// use light_tool::blake3;
+/// let data = vec![0u8; 1 << 20];
+/// println!("blake3: {:?}", blake3::hash_parallel(&data, 4))
+/// ```
+pub fn hash_parallel(input: &[u8], threads: usize) -> [u8; OUT_LEN] {
+    let mut hasher = Blake3::new();
+    hasher.update_parallel(input, threads);
+    hasher.finalize()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::digest::test_data as input;
+    use crate::hex::encode as hex;
+
+    /// 官方测试向量：(长度, hash, keyed_hash, derive_key)
+    const VECTORS: [(usize, &str, &str, &str); 9] = [
+        (0, "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262", "92b2b75604ed3c761f9d6f62392c8a9227ad0ea3f09573e783f1498a4ed60d26", "2cc39783c223154fea8dfb7c1b1660f2ac2dcbd1c1de8277b0b0dd39b7e50d7d"),
+        (1, "2d3adedff11b61f14c886e35afa036736dcd87a74d27b5c1510225d0f592e213", "6d7878dfff2f485635d39013278ae14f1454b8c0a3a2d34bc1ab38228a80c95b", "b3e2e340a117a499c6cf2398a19ee0d29cca2bb7404c73063382693bf66cb06c"),
+        (1023, "10108970eeda3eb932baac1428c7a2163b0e924c9a9e25b35bba72b28f70bd11", "c951ecdf03288d0fcc96ee3413563d8a6d3589547f2c2fb36d9786470f1b9d6e", "74a16c1c3d44368a86e1ca6df64be6a2f64cce8f09220787450722d85725dea5"),
+        (1024, "42214739f095a406f3fc83deb889744ac00df831c10daa55189b5d121c855af7", "75c46f6f3d9eb4f55ecaaee480db732e6c2105546f1e675003687c31719c7ba4", "7356cd7720d5b66b6d0697eb3177d9f8d73a4a5c5e968896eb6a689684302706"),
+        (1025, "d00278ae47eb27b34faecf67b4fe263f82d5412916c1ffd97c8cb7fb814b8444", "357dc55de0c7e382c900fd6e320acc04146be01db6a8ce7210b7189bd664ea69", "effaa245f065fbf82ac186839a249707c3bddf6d3fdda22d1b95a3c970379bcb"),
+        (2049, "5f4d72f40d7a5f82b15ca2b2e44b1de3c2ef86c426c95c1af0b6879522563030", "9f29700902f7c86e514ddc4df1e3049f258b2472b6dd5267f61bf13983b78dd5", "2ea477c5515cc3dd606512ee72bb3e0e758cfae7232826f35fb98ca1bcbdf273"),
+        (8193, "bab6c09cb8ce8cf459261398d2e7aef35700bf488116ceb94a36d0f5f1b7bc3b", "954a2a75420c8d6547e3ba5b98d963e6fa6491addc8c023189cc519821b4a1f5", "af1e0346e389b17c23200270a64aa4e1ead98c61695d917de7d5b00491c9b0f1"),
+        (31744, "62b6960e1a44bcc1eb1a611a8d6235b6b4b78f32e7abc4fb4c6cdcce94895c47", "efa53b389ab67c593dba624d898d0f7353ab99e4ac9d42302ee64cbf9939a419", "39772aef80e0ebe60596361e45b061e8f417429d529171b6764468c22928e28e"),
+        (102400, "bc3e3d41a1146b069abffad3c0d44860cf664390afce4d9661f7902e7943e085", "1c35d1a5811083fd7119f5d5d1ba027b4d01c0c6c49fb6ff2cf75393ea5db4a7", "4652cff7a3f385a6103b5c260fc1593e13c778dbe608efb092fe7ee69df6e9c6"),
+    ];
+
+    const KEY: &[u8; 32] = b"whats the Elvish word for friend";
+    const CONTEXT: &str = "BLAKE3 2019-12-27 16:29:52 test vectors context";
+
+    #[test]
+    fn test_blake3() {
+        for (len, hash, keyed_hash, derive_key) in VECTORS {
+            let data = input(len);
+            assert_eq!(blake3(&data), hash, "len: {}", len);
+
+            let mut hasher = Blake3::new_keyed(KEY);
+            hasher.update(&data);
+            assert_eq!(hex(hasher.finalize()), keyed_hash, "len: {}", len);
+
+            let mut hasher = Blake3::new_derive_key(CONTEXT);
+            hasher.update(&data);
+            assert_eq!(hex(hasher.finalize()), derive_key, "len: {}", len);
+        }
+    }
+
+    #[test]
+    fn test_blake3_streaming_and_parallel() {
+        for (len, hash, _, _) in VECTORS {
+            let data = input(len);
+            for chunk_size in [1, 63, 1024, 1025, 5000] {
+                let mut hasher = Blake3::new();
+                for chunk in data.chunks(chunk_size) {
+                    hasher.update_parallel(chunk, 2);
+                }
+                assert_eq!(hex(hasher.finalize()), hash, "len: {}, chunk: {}", len, chunk_size);
+            }
+            for threads in [1, 3, 8] {
+                assert_eq!(hex(hash_parallel(&data, threads)), hash, "len: {}, threads: {}", len, threads);
+            }
+        }
+
+        // 线程数不超过 CPU 核数与完整块数
+        let cores = thread::available_parallelism().map_or(1, |n| n.get());
+        assert_eq!(thread_limit(usize::MAX, 1 << 30), cores);
+        assert_eq!(thread_limit(8, CHUNK_LEN * 2 - 1), 1);
+        assert_eq!(thread_limit(0, 1 << 20), 1);
+        assert_eq!(hex(hash_parallel(&input(102400), usize::MAX)), VECTORS[8].1);
+    }
+
+    #[test]
+    fn test_blake3_xof() {
+        let mut hasher = Blake3::new();
+        hasher.update(&input(1025));
+        let mut reader = hasher.finalize_xof();
+        let mut output = [0u8; 80];
+        for chunk in output.chunks_mut(7) {
+            reader.read(chunk);
+        }
+        assert_eq!(hex(output), "d00278ae47eb27b34faecf67b4fe263f82d5412916c1ffd97c8cb7fb814b8444f4c4a22b4b399155358a994e52bf255de60035742ec71bd08ac275a1b51cc6bfe332b0ef84b409108cda080e6269ed4b");
+    }
+}
@@ -0,0 +1,685 @@
+use std::hash::{BuildHasher, BuildHasherDefault, Hasher};
+
+/// 生成 slicing-by-8 查找表
+const fn crc_tables(poly: u32) -> [[u32; 256]; 8] {
+    let mut tables = [[0u32; 256]; 8];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut k = 0;
+        while k < 8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ poly } else { crc >> 1 };
+            k += 1;
+        }
+        tables[0][i] = crc;
+        i += 1;
+    }
+    let mut i = 0;
+    while i < 256 {
+        let mut t = 1;
+        while t < 8 {
+            let prev = tables[t - 1][i];
+            tables[t][i] = (prev >> 8) ^ tables[0][(prev & 0xff) as usize];
+            t += 1;
+        }
+        i += 1;
+    }
+    tables
+}
+
+/// CRC-32 (IEEE 802.3) 查找表
+static CRC32_TABLES: [[u32; 256]; 8] = crc_tables(0xedb88320);
+
+/// CRC-32C (Castagnoli) 查找表
+static CRC32C_TABLES: [[u32; 256]; 8] = crc_tables(0x82f63b78);
+
+/// 每次处理 8 字节的查表 CRC
+fn crc_update(mut crc: u32, mut input: &[u8], t: &[[u32; 256]; 8]) -> u32 {
+    while input.len() >= 8 {
+        let a = crc ^ u32::from_le_bytes(input[..4].try_into().unwrap());
+        let b = u32::from_le_bytes(input[4..8].try_into().unwrap());
+        crc = t[7][(a & 0xff) as usize]
+            ^ t[6][((a >> 8) & 0xff) as usize]
+            ^ t[5][((a >> 16) & 0xff) as usize]
+            ^ t[4][(a >> 24) as usize]
+            ^ t[3][(b & 0xff) as usize]
+            ^ t[2][((b >> 8) & 0xff) as usize]
+            ^ t[1][((b >> 16) & 0xff) as usize]
+            ^ t[0][(b >> 24) as usize];
+        input = &input[8..];
+    }
+    for &byte in input {
+        crc = t[0][((crc ^ byte as u32) & 0xff) as usize] ^ (crc >> 8);
+    }
+    crc
+}
+
+/// 为校验和结构体实现 `std::io::Write`
+macro_rules! impl_write {
+    ($($name:ty),*) => {
+        $(
+            impl std::io::Write for $name {
+                fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                    self.update(buf);
+                    Ok(buf.len())
+                }
+
+                fn flush(&mut self) -> std::io::Result<()> {
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+/// CRC-32 结构体（zlib、gzip、PNG 使用的 IEEE 多项式）
+///
+/// # Example
+/// ```rust
+/// use light_tool::checksum::Crc32;
+/// let mut crc = Crc32::new();
+/// crc.update(b"1234");
+/// crc.update(b"56789");
+/// assert_eq!(crc.finalize(), 0xcbf43926)
+/// ```
+#[derive(Clone, Copy, Default)]
+pub struct Crc32 {
+    crc: u32,
+}
+
+impl Crc32 {
+    /// 初始化
+    pub fn new() -> Self {
+        Self { crc: 0 }
+    }
+
+    /// 写入数据
+    pub fn update(&mut self, input: &[u8]) {
+        self.crc = !crc_update(!self.crc, input, &CRC32_TABLES);
+    }
+
+    /// 计算校验值
+    pub fn finalize(self) -> u32 {
+        self.crc
+    }
+}
+
+/// CRC-32C 结构体（iSCSI、ext4、Kafka 使用的 Castagnoli 多项式）
+#[derive(Clone, Copy, Default)]
+pub struct Crc32c {
+    crc: u32,
+}
+
+impl Crc32c {
+    /// 初始化
+    pub fn new() -> Self {
+        Self { crc: 0 }
+    }
+
+    /// 写入数据
+    pub fn update(&mut self, input: &[u8]) {
+        self.crc = !crc_update(!self.crc, input, &CRC32C_TABLES);
+    }
+
+    /// 计算校验值
+    pub fn finalize(self) -> u32 {
+        self.crc
+    }
+}
+
+/// Adler-32 结构体
+#[derive(Clone, Copy)]
+pub struct Adler32 {
+    a: u32,
+    b: u32,
+}
+
+impl Default for Adler32 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Adler32 {
+    /// 初始化
+    pub fn new() -> Self {
+        Self { a: 1, b: 0 }
+    }
+
+    /// 写入数据
+    pub fn update(&mut self, input: &[u8]) {
+        const MOD: u32 = 65521;
+        // 5552 是保证 b 在取模前不溢出 u32 的最大字节数
+        for chunk in input.chunks(5552) {
+            for &byte in chunk {
+                self.a += byte as u32;
+                self.b += self.a;
+            }
+            self.a %= MOD;
+            self.b %= MOD;
+        }
+    }
+
+    /// 计算校验值
+    pub fn finalize(self) -> u32 {
+        (self.b << 16) | self.a
+    }
+}
+
+/// FNV-1a 32 位结构体
+#[derive(Clone, Copy)]
+pub struct Fnv1a32 {
+    hash: u32,
+}
+
+impl Default for Fnv1a32 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Fnv1a32 {
+    /// 初始化
+    pub fn new() -> Self {
+        Self { hash: 0x811c9dc5 }
+    }
+
+    /// 写入数据
+    pub fn update(&mut self, input: &[u8]) {
+        for &byte in input {
+            self.hash = (self.hash ^ byte as u32).wrapping_mul(0x01000193);
+        }
+    }
+
+    /// 计算哈希值
+    pub fn finalize(self) -> u32 {
+        self.hash
+    }
+}
+
+/// FNV-1a 64 位结构体，适合短键的哈希表
+///
+/// # Example
+/// ```rust
+/// use std::collections::HashMap;
+/// use light_tool::checksum::FnvBuildHasher;
+/// let mut map: HashMap<&str, i32, FnvBuildHasher> = HashMap::default();
+/// map.insert("key", 1);
+/// assert_eq!(map["key"], 1)
+/// ```
+#[derive(Clone, Copy)]
+pub struct Fnv1a64 {
+    hash: u64,
+}
+
+impl Default for Fnv1a64 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Fnv1a64 {
+    /// 初始化
+    pub fn new() -> Self {
+        Self { hash: 0xcbf29ce484222325 }
+    }
+
+    /// 写入数据
+    pub fn update(&mut self, input: &[u8]) {
+        for &byte in input {
+            self.hash = (self.hash ^ byte as u64).wrapping_mul(0x100000001b3);
+        }
+    }
+
+    /// 计算哈希值
+    pub fn finalize(self) -> u64 {
+        self.hash
+    }
+}
+
+impl Hasher for Fnv1a64 {
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        self.update(bytes);
+    }
+}
+
+/// 使用 FNV-1a 的 `BuildHasher`
+pub type FnvBuildHasher = BuildHasherDefault<Fnv1a64>;
+
+const XXH_PRIME64_1: u64 = 0x9e3779b185ebca87;
+const XXH_PRIME64_2: u64 = 0xc2b2ae3d27d4eb4f;
+const XXH_PRIME64_3: u64 = 0x165667b19e3779f9;
+const XXH_PRIME64_4: u64 = 0x85ebca77c2b2ae63;
+const XXH_PRIME64_5: u64 = 0x27d4eb2f165667c5;
+
+fn xxh64_round(acc: u64, input: u64) -> u64 {
+    acc.wrapping_add(input.wrapping_mul(XXH_PRIME64_2))
+        .rotate_left(31)
+        .wrapping_mul(XXH_PRIME64_1)
+}
+
+fn xxh64_merge_round(acc: u64, val: u64) -> u64 {
+    (acc ^ xxh64_round(0, val))
+        .wrapping_mul(XXH_PRIME64_1)
+        .wrapping_add(XXH_PRIME64_4)
+}
+
+/// xxHash64 结构体
+///
+/// # Example
+/// ```rust
+/// use light_tool::checksum::XxHash64;
+/// let mut xxh = XxHash64::with_seed(0);
+/// xxh.update(b"abc");
+/// assert_eq!(xxh.finalize(), 0x44bc2cf5ad770999)
+/// ```
+#[derive(Clone, Copy)]
+pub struct XxHash64 {
+    seed: u64,
+    acc: [u64; 4],        // 4 路累加器
+    buffer: [u8; 32],     // 未满 32 字节的数据
+    buffer_len: usize,
+    len: u64,             // 消息总字节数
+}
+
+impl Default for XxHash64 {
+    fn default() -> Self {
+        Self::with_seed(0)
+    }
+}
+
+impl XxHash64 {
+    /// 使用种子初始化
+    pub fn with_seed(seed: u64) -> Self {
+        Self {
+            seed,
+            acc: [
+                seed.wrapping_add(XXH_PRIME64_1).wrapping_add(XXH_PRIME64_2),
+                seed.wrapping_add(XXH_PRIME64_2),
+                seed,
+                seed.wrapping_sub(XXH_PRIME64_1),
+            ],
+            buffer: [0u8; 32],
+            buffer_len: 0,
+            len: 0,
+        }
+    }
+
+    fn consume(acc: &mut [u64; 4], stripe: &[u8]) {
+        for (i, lane) in acc.iter_mut().enumerate() {
+            *lane = xxh64_round(*lane, u64::from_le_bytes(stripe[i * 8..i * 8 + 8].try_into().unwrap()));
+        }
+    }
+
+    /// 写入数据
+    pub fn update(&mut self, mut input: &[u8]) {
+        self.len = self.len.wrapping_add(input.len() as u64);
+
+        if self.buffer_len > 0 {
+            let take = (32 - self.buffer_len).min(input.len());
+            self.buffer[self.buffer_len..self.buffer_len + take].copy_from_slice(&input[..take]);
+            self.buffer_len += take;
+            input = &input[take..];
+            if self.buffer_len < 32 {
+                return;
+            }
+            let stripe = self.buffer;
+            Self::consume(&mut self.acc, &stripe);
+            self.buffer_len = 0;
+        }
+
+        let mut stripes = input.chunks_exact(32);
+        for stripe in &mut stripes {
+            Self::consume(&mut self.acc, stripe);
+        }
+        let rest = stripes.remainder();
+        self.buffer[..rest.len()].copy_from_slice(rest);
+        self.buffer_len = rest.len();
+    }
+
+    /// 计算哈希值
+    pub fn finalize(self) -> u64 {
+        let mut hash = if self.len >= 32 {
+            let [a, b, c, d] = self.acc;
+            let mut hash = a
+                .rotate_left(1)
+                .wrapping_add(b.rotate_left(7))
+                .wrapping_add(c.rotate_left(12))
+                .wrapping_add(d.rotate_left(18));
+            for lane in self.acc {
+                hash = xxh64_merge_round(hash, lane);
+            }
+            hash
+        } else {
+            self.seed.wrapping_add(XXH_PRIME64_5)
+        };
+        hash = hash.wrapping_add(self.len);
+
+        let mut rest = &self.buffer[..self.buffer_len];
+        while rest.len() >= 8 {
+            let k = xxh64_round(0, u64::from_le_bytes(rest[..8].try_into().unwrap()));
+            hash = (hash ^ k)
+                .rotate_left(27)
+                .wrapping_mul(XXH_PRIME64_1)
+                .wrapping_add(XXH_PRIME64_4);
+            rest = &rest[8..];
+        }
+        if rest.len() >= 4 {
+            let k = u32::from_le_bytes(rest[..4].try_into().unwrap()) as u64;
+            hash = (hash ^ k.wrapping_mul(XXH_PRIME64_1))
+                .rotate_left(23)
+                .wrapping_mul(XXH_PRIME64_2)
+                .wrapping_add(XXH_PRIME64_3);
+            rest = &rest[4..];
+        }
+        for &byte in rest {
+            hash = (hash ^ (byte as u64).wrapping_mul(XXH_PRIME64_5))
+                .rotate_left(11)
+                .wrapping_mul(XXH_PRIME64_1);
+        }
+
+        hash ^= hash >> 33;
+        hash = hash.wrapping_mul(XXH_PRIME64_2);
+        hash ^= hash >> 29;
+        hash = hash.wrapping_mul(XXH_PRIME64_3);
+        hash ^ (hash >> 32)
+    }
+}
+
+impl Hasher for XxHash64 {
+    fn finish(&self) -> u64 {
+        self.finalize()
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        self.update(bytes);
+    }
+}
+
+/// 使用 xxHash64 的 `BuildHasher`，可指定种子
+#[derive(Clone, Copy, Default)]
+pub struct XxHash64Builder {
+    seed: u64,
+}
+
+impl XxHash64Builder {
+    /// 使用种子创建
+    pub fn with_seed(seed: u64) -> Self {
+        Self { seed }
+    }
+}
+
+impl BuildHasher for XxHash64Builder {
+    type Hasher = XxHash64;
+
+    fn build_hasher(&self) -> XxHash64 {
+        XxHash64::with_seed(self.seed)
+    }
+}
+
+/// MurmurHash3 x86 32 位结构体
+#[derive(Clone, Copy)]
+pub struct Murmur3 {
+    hash: u32,
+    tail: [u8; 4],    // 未满 4 字节的数据
+    tail_len: usize,
+    len: u32,         // 消息总字节数（按算法定义截断为 32 位）
+}
+
+impl Default for Murmur3 {
+    fn default() -> Self {
+        Self::with_seed(0)
+    }
+}
+
+const MURMUR_C1: u32 = 0xcc9e2d51;
+const MURMUR_C2: u32 = 0x1b873593;
+
+fn murmur3_mix(k: u32) -> u32 {
+    k.wrapping_mul(MURMUR_C1).rotate_left(15).wrapping_mul(MURMUR_C2)
+}
+
+impl Murmur3 {
+    /// 使用种子初始化
+    pub fn with_seed(seed: u32) -> Self {
+        Self { hash: seed, tail: [0u8; 4], tail_len: 0, len: 0 }
+    }
+
+    fn block(&mut self, block: &[u8]) {
+        self.hash ^= murmur3_mix(u32::from_le_bytes(block.try_into().unwrap()));
+        self.hash = self.hash.rotate_left(13).wrapping_mul(5).wrapping_add(0xe6546b64);
+    }
+
+    /// 写入数据
+    pub fn update(&mut self, mut input: &[u8]) {
+        self.len = self.len.wrapping_add(input.len() as u32);
+
+        if self.tail_len > 0 {
+            let take = (4 - self.tail_len).min(input.len());
+            self.tail[self.tail_len..self.tail_len + take].copy_from_slice(&input[..take]);
+            self.tail_len += take;
+            input = &input[take..];
+            if self.tail_len < 4 {
+                return;
+            }
+            let block = self.tail;
+            self.block(&block);
+            self.tail_len = 0;
+        }
+
+        let mut blocks = input.chunks_exact(4);
+        for block in &mut blocks {
+            self.block(block);
+        }
+        let rest = blocks.remainder();
+        self.tail[..rest.len()].copy_from_slice(rest);
+        self.tail_len = rest.len();
+    }
+
+    /// 计算哈希值
+    pub fn finalize(self) -> u32 {
+        let mut hash = self.hash;
+        if self.tail_len > 0 {
+            let mut k = 0u32;
+            for (i, &byte) in self.tail[..self.tail_len].iter().enumerate() {
+                k |= (byte as u32) << (8 * i);
+            }
+            hash ^= murmur3_mix(k);
+        }
+
+        hash ^= self.len;
+        hash ^= hash >> 16;
+        hash = hash.wrapping_mul(0x85ebca6b);
+        hash ^= hash >> 13;
+        hash = hash.wrapping_mul(0xc2b2ae35);
+        hash ^ (hash >> 16)
+    }
+}
+
+impl Hasher for Murmur3 {
+    fn finish(&self) -> u64 {
+        self.finalize() as u64
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        self.update(bytes);
+    }
+}
+
+impl_write!(Crc32, Crc32c, Adler32, Fnv1a32, Fnv1a64, XxHash64, Murmur3);
+
+/// CRC-32 校验值
+///
+/// # Example
+/// ```rust
+/// use light_tool::checksum;
+/// assert_eq!(checksum::crc32("123456789"), 0xcbf43926)
+/// ```
+pub fn crc32<T: AsRef<[u8]>>(input: T) -> u32 {
+    let mut crc = Crc32::new();
+    crc.update(input.as_ref());
+    crc.finalize()
+}
+
+/// CRC-32C 校验值
+///
+/// # Example
+/// ```rust
+/// use light_tool::checksum;
+/// assert_eq!(checksum::crc32c("123456789"), 0xe3069283)
+/// ```
+pub fn crc32c<T: AsRef<[u8]>>(input: T) -> u32 {
+    let mut crc = Crc32c::new();
+    crc.update(input.as_ref());
+    crc.finalize()
+}
+
+/// Adler-32 校验值
+///
+/// # Example
+/// ```rust
+/// use light_tool::checksum;
+/// assert_eq!(checksum::adler32("Wikipedia"), 0x11e60398)
+/// ```
+pub fn adler32<T: AsRef<[u8]>>(input: T) -> u32 {
+    let mut adler = Adler32::new();
+    adler.update(input.as_ref());
+    adler.finalize()
+}
+
+/// FNV-1a 32 位哈希值
+///
+/// # Example
+/// ```rust
+/// use light_tool::checksum;
+/// println!("fnv1a: {}", checksum::fnv1a_32("Hello, world!"))
+/// ```
+pub fn fnv1a_32<T: AsRef<[u8]>>(input: T) -> u32 {
+    let mut fnv = Fnv1a32::new();
+    fnv.update(input.as_ref());
+    fnv.finalize()
+}
+
+/// FNV-1a 64 位哈希值
+///
+/// # Example
+/// ```rust
+/// use light_tool::checksum;
+/// println!("fnv1a: {}", checksum::fnv1a_64("Hello, world!"))
+/// ```
+pub fn fnv1a_64<T: AsRef<[u8]>>(input: T) -> u64 {
+    let mut fnv = Fnv1a64::new();
+    fnv.update(input.as_ref());
+    fnv.finalize()
+}
+
+/// xxHash64 哈希值
+///
+/// # Example
+/// ```rust
+/// use light_tool::checksum;
+/// println!("xxhash64: {}", checksum::xxhash64("Hello, world!", 0))
+/// ```
+pub fn xxhash64<T: AsRef<[u8]>>(input: T, seed: u64) -> u64 {
+    let mut xxh = XxHash64::with_seed(seed);
+    xxh.update(input.as_ref());
+    xxh.finalize()
+}
+
+/// MurmurHash3 x86 32 位哈希值
+///
+/// # Example
+/// ```rust
+/// use light_tool::checksum;
+/// println!("murmur3: {}", checksum::murmur3_32("Hello, world!", 0))
+/// ```
+pub fn murmur3_32<T: AsRef<[u8]>>(input: T, seed: u32) -> u32 {
+    let mut murmur = Murmur3::with_seed(seed);
+    murmur.update(input.as_ref());
+    murmur.finalize()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use super::*;
+
+    fn data() -> Vec<u8> {
+        crate::digest::test_data(1000)
+    }
+
+    #[test]
+    fn test_crc32() {
+        assert_eq!(crc32(""), 0);
+        assert_eq!(crc32("123456789"), 0xcbf43926);
+        assert_eq!(crc32(data()), 0x721746a6);
+        assert_eq!(crc32c("123456789"), 0xe3069283);
+        assert_eq!(crc32c(data()), 0x11f66220);
+    }
+
+    #[test]
+    fn test_adler32() {
+        assert_eq!(adler32(""), 1);
+        assert_eq!(adler32("Wikipedia"), 0x11e60398);
+        assert_eq!(adler32(vec![0xff; 100_000]), 0x149a302c);
+    }
+
+    #[test]
+    fn test_fnv1a() {
+        assert_eq!(fnv1a_32(""), 0x811c9dc5);
+        assert_eq!(fnv1a_32("a"), 0xe40c292c);
+        assert_eq!(fnv1a_64("a"), 0xaf63dc4c8601ec8c);
+        assert_eq!(fnv1a_64("foobar"), 0x85944171f73967e8);
+    }
+
+    #[test]
+    fn test_xxhash64() {
+        assert_eq!(xxhash64("", 0), 0xef46db3751d8e999);
+        assert_eq!(xxhash64("abc", 0), 0x44bc2cf5ad770999);
+        assert_eq!(xxhash64(data(), 0), 0xf306f04aa88b54d3);
+        assert_eq!(xxhash64(data(), 2654435761), 0x1c04ceb2c089c610);
+    }
+
+    #[test]
+    fn test_murmur3() {
+        assert_eq!(murmur3_32("", 0), 0);
+        assert_eq!(murmur3_32("hello", 0), 0x248bfa47);
+        assert_eq!(murmur3_32("The quick brown fox jumps over the lazy dog", 0x9747b28c), 0x2fa826cd);
+        assert_eq!(murmur3_32(data(), 42), 0x878c7726);
+    }
+
+    #[test]
+    fn test_streaming() {
+        use crate::digest::assert_chunked;
+
+        // xxHash64 按 32 字节分条处理，MurmurHash3 按 4 字节分块处理，分块大小跨越这些边界
+        let data = data();
+        let chunk_sizes = [1, 3, 7, 31, 32, 33, 500];
+        assert_chunked(Crc32::new, Crc32::update, Crc32::finalize, &data, &chunk_sizes);
+        assert_chunked(Crc32c::new, Crc32c::update, Crc32c::finalize, &data, &chunk_sizes);
+        assert_chunked(Adler32::new, Adler32::update, Adler32::finalize, &data, &chunk_sizes);
+        assert_chunked(Fnv1a64::new, Fnv1a64::update, Fnv1a64::finalize, &data, &chunk_sizes);
+        for len in [0, 3, 4, 31, 32, 33, 1000] {
+            assert_chunked(|| XxHash64::with_seed(7), XxHash64::update, XxHash64::finalize, &data[..len], &chunk_sizes);
+            assert_chunked(|| Murmur3::with_seed(7), Murmur3::update, Murmur3::finalize, &data[..len], &chunk_sizes);
+        }
+    }
+
+    #[test]
+    fn test_build_hasher() {
+        let mut map: HashMap<String, usize, XxHash64Builder> = HashMap::with_hasher(XxHash64Builder::with_seed(1));
+        let mut fnv_map: HashMap<String, usize, FnvBuildHasher> = HashMap::default();
+        for i in 0..100 {
+            map.insert(format!("key-{}", i), i);
+            fnv_map.insert(format!("key-{}", i), i);
+        }
+        for i in 0..100 {
+            assert_eq!(map[&format!("key-{}", i)], i);
+            assert_eq!(fnv_map[&format!("key-{}", i)], i);
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use std::hint::black_box;
+
+/// 常量时间比较
+///
+/// 耗时只与长度有关，不会因第一个不同字节的位置而提前返回；长度不同时直接返回 `false`
+///
+/// # Example
+/// ```rust
+/// use light_tool::{ct, md5};
+/// let stored = "65a8e27d8879283831b664bd8b7f0ad4";
+/// assert!(ct::eq(md5::str("Hello, World!"), stored));
+/// assert!(!ct::eq(md5::str("Hello"), stored))
+/// ```
+pub fn eq<A: AsRef<[u8]>, B: AsRef<[u8]>>(a: A, b: B) -> bool {
+    let (a, b) = (a.as_ref(), b.as_ref());
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut diff = 0u8;
+    for (x, y) in a.iter().zip(b) {
+        // 阻止编译器将循环优化为提前退出的比较
+        diff |= black_box(x ^ y);
+    }
+    diff == 0
+}
+
+/// 为摘要结果（`[u8; N]`、`Vec<u8>`、十六进制字符串等）提供常量时间比较
+///
+/// # Example
+/// ```rust
+/// use light_tool::ct::ConstantTimeEq;
+/// use light_tool::digest::Digest;
+/// use light_tool::hmac::{self, Sha256};
+/// assert!(Sha256::digest("abc").ct_eq(Sha256::digest("abc")));
+/// assert!(hmac::sign::<Sha256, _, _>("key", "data").ct_eq(hmac::hmac_sha256("key", "data")));
+/// assert!(!hmac::sha256("abc").ct_eq(hmac::sha256("abd")))
+/// ```
+pub trait ConstantTimeEq {
+    fn ct_eq<O: AsRef<[u8]>>(&self, other: O) -> bool;
+}
+
+impl<T: AsRef<[u8]> + ?Sized> ConstantTimeEq for T {
+    fn ct_eq<O: AsRef<[u8]>>(&self, other: O) -> bool {
+        eq(self, other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eq() {
+        assert!(eq("", ""));
+        assert!(eq([1u8, 2, 3], vec![1u8, 2, 3]));
+        assert!(!eq([1u8, 2, 3], [1u8, 2, 4]));
+        assert!(!eq([1u8, 2, 3], [1u8, 2]));
+        assert!("abc".ct_eq(b"abc"));
+        assert!(!String::from("abc").ct_eq("ABC"));
+    }
+}
@@ -0,0 +1,200 @@
+//! Ed25519 与 X25519 共用的 GF(2^255 - 19) 域运算及密钥编码
+//!
+//! 域元素以 16 个 16 位分段表示（参考 TweetNaCl），所有运算不依赖秘密数据分支
+
+use std::ops::{Add, Mul, Neg, Sub};
+
+#[derive(Clone, Copy)]
+pub(crate) struct Fe(pub(crate) [i64; 16]);
+
+pub(crate) const ZERO: Fe = Fe([0; 16]);
+pub(crate) const ONE: Fe = Fe([1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+
+impl Fe {
+    /// 解码 32 字节小端整数，忽略最高位
+    pub(crate) fn from_bytes(bytes: &[u8; 32]) -> Fe {
+        let mut limbs = [0i64; 16];
+        for (i, limb) in limbs.iter_mut().enumerate() {
+            *limb = bytes[2 * i] as i64 | (bytes[2 * i + 1] as i64) << 8;
+        }
+        limbs[15] &= 0x7fff;
+        Fe(limbs)
+    }
+
+    /// 编码为规范（小于 p）的 32 字节小端整数
+    pub(crate) fn to_bytes(self) -> [u8; 32] {
+        let mut t = self;
+        t.carry();
+        t.carry();
+        t.carry();
+        for _ in 0..2 {
+            // m = t - p，根据是否借位选择 t 或 m
+            let mut m = [0i64; 16];
+            m[0] = t.0[0] - 0xffed;
+            for i in 1..15 {
+                m[i] = t.0[i] - 0xffff - ((m[i - 1] >> 16) & 1);
+                m[i - 1] &= 0xffff;
+            }
+            m[15] = t.0[15] - 0x7fff - ((m[14] >> 16) & 1);
+            let borrow = (m[15] >> 16) & 1;
+            m[14] &= 0xffff;
+            let mut m = Fe(m);
+            Fe::swap(&mut t, &mut m, 1 - borrow);
+        }
+        let mut bytes = [0u8; 32];
+        for i in 0..16 {
+            bytes[2 * i] = t.0[i] as u8;
+            bytes[2 * i + 1] = (t.0[i] >> 8) as u8;
+        }
+        bytes
+    }
+
+    fn carry(&mut self) {
+        for i in 0..16 {
+            self.0[i] += 1 << 16;
+            let c = self.0[i] >> 16;
+            if i < 15 {
+                self.0[i + 1] += c - 1;
+            } else {
+                self.0[0] += 38 * (c - 1);
+            }
+            self.0[i] -= c << 16;
+        }
+    }
+
+    /// `choice` 为 1 时交换 a、b，为 0 时不变
+    pub(crate) fn swap(a: &mut Fe, b: &mut Fe, choice: i64) {
+        let mask = !(choice - 1);
+        for i in 0..16 {
+            let t = mask & (a.0[i] ^ b.0[i]);
+            a.0[i] ^= t;
+            b.0[i] ^= t;
+        }
+    }
+
+    pub(crate) fn square(self) -> Fe {
+        self * self
+    }
+
+    /// 最低位，用于 Ed25519 中 x 坐标的符号
+    pub(crate) fn is_negative(self) -> u8 {
+        self.to_bytes()[0] & 1
+    }
+
+    /// 乘法逆元，a^(p - 2)
+    pub(crate) fn invert(self) -> Fe {
+        let mut c = self;
+        for a in (0..254).rev() {
+            c = c.square();
+            if a != 2 && a != 4 {
+                c = c * self;
+            }
+        }
+        c
+    }
+
+    /// a^((p - 5) / 8)，用于开平方
+    pub(crate) fn pow_p58(self) -> Fe {
+        let mut c = self;
+        for a in (0..251).rev() {
+            c = c.square();
+            if a != 1 {
+                c = c * self;
+            }
+        }
+        c
+    }
+}
+
+impl PartialEq for Fe {
+    fn eq(&self, other: &Fe) -> bool {
+        self.to_bytes() == other.to_bytes()
+    }
+}
+
+impl Add for Fe {
+    type Output = Fe;
+
+    fn add(self, b: Fe) -> Fe {
+        let mut o = self;
+        for i in 0..16 {
+            o.0[i] += b.0[i];
+        }
+        o
+    }
+}
+
+impl Sub for Fe {
+    type Output = Fe;
+
+    fn sub(self, b: Fe) -> Fe {
+        let mut o = self;
+        for i in 0..16 {
+            o.0[i] -= b.0[i];
+        }
+        o
+    }
+}
+
+impl Neg for Fe {
+    type Output = Fe;
+
+    fn neg(self) -> Fe {
+        ZERO - self
+    }
+}
+
+impl Mul for Fe {
+    type Output = Fe;
+
+    fn mul(self, b: Fe) -> Fe {
+        let mut t = [0i64; 31];
+        for i in 0..16 {
+            for j in 0..16 {
+                t[i + j] += self.0[i] * b.0[j];
+            }
+        }
+        // 2^256 ≡ 38 (mod p)
+        for i in 0..15 {
+            t[i] += 38 * t[i + 16];
+        }
+        let mut o = Fe(t[..16].try_into().unwrap());
+        o.carry();
+        o.carry();
+        o
+    }
+}
+
+/// 为密钥/签名类型生成十六进制与 Base64 的导入导出方法，要求类型实现 `from_slice` 与 `to_bytes`
+macro_rules! impl_key_encoding {
+    ($type:ty) => {
+        impl $type {
+            /// 从十六进制字符串导入
+            pub fn from_hex(input: &str) -> Result<Self, String> {
+                Self::from_slice(&crate::hex::decode(input).map_err(|e| e.to_string())?)
+            }
+
+            /// 从 Base64 字符串导入
+            pub fn from_base64(input: &str) -> Result<Self, String> {
+                Self::from_slice(&crate::base64::decode_bytes(input).map_err(|e| e.to_string())?)
+            }
+
+            /// 导出为十六进制字符串
+            pub fn to_hex(&self) -> String {
+                crate::hex::encode(self.to_bytes())
+            }
+
+            /// 导出为 Base64 字符串
+            pub fn to_base64(&self) -> String {
+                crate::base64::encode_bytes(&self.to_bytes())
+            }
+        }
+    };
+}
+
+pub(crate) use impl_key_encoding;
+
+/// 将切片转换为定长数组，长度不符时返回错误，`name` 为错误信息中的名称（如 `Ed25519 signature`）
+pub(crate) fn to_array<const N: usize>(bytes: &[u8], name: &str) -> Result<[u8; N], String> {
+    bytes.try_into().map_err(|_| format!("Invalid {} length: expected {} bytes, got {}", name, N, bytes.len()))
+}
@@ -0,0 +1,289 @@
+use std::path::Path;
+use std::{fmt, fs, io};
+use crate::base64::{self, DecodeMode};
+use crate::percent::{self, EncodeSet};
+
+/// 未声明媒体类型时的默认值 (RFC 2397)
+const DEFAULT_MIME_TYPE: &str = "text/plain";
+
+/// 非 Base64 数据的编码集合，`#` 与 `%` 也需要编码
+const DATA: EncodeSet = EncodeSet::URI.add(b'#').add(b'%');
+
+/// `data:` URI (RFC 2397)
+///
+/// # Example
+/// ```rust
+/// use light_tool::data_uri::DataUri;
+/// let uri = DataUri::new("text/plain", "Hello, 世界").with_parameter("charset", "utf-8");
+/// assert_eq!(uri.to_string(), "data:text/plain;charset=utf-8;base64,SGVsbG8sIOS4lueVjA==");
+///
+/// let parsed = DataUri::parse("data:,Hello%2C%20World").unwrap();
+/// assert_eq!(parsed.mime_type, "text/plain");
+/// assert_eq!(parsed.data, b"Hello, World")
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DataUri {
+    /// 媒体类型，例如 `image/png`
+    pub mime_type: String,
+    /// 媒体类型参数，例如 `charset=utf-8`
+    pub parameters: Vec<(String, String)>,
+    /// 数据部分是否使用 Base64 编码，否则使用百分号编码
+    pub base64: bool,
+    /// 解码后的数据
+    pub data: Vec<u8>,
+}
+
+impl DataUri {
+    /// 创建 Base64 编码的 data URI
+    pub fn new<T: Into<Vec<u8>>>(mime_type: &str, data: T) -> Self {
+        DataUri {
+            mime_type: mime_type.to_string(),
+            parameters: Vec::new(),
+            base64: true,
+            data: data.into(),
+        }
+    }
+
+    /// 添加媒体类型参数
+    pub fn with_parameter(mut self, name: &str, value: &str) -> Self {
+        self.parameters.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// 数据部分改用百分号编码，适合较短的文本（如 SVG）
+    pub fn percent_encoded(mut self) -> Self {
+        self.base64 = false;
+        self
+    }
+
+    /// 读取文件生成 data URI，媒体类型根据扩展名与文件头检测
+    ///
+    /// # Example
+    /// ```txt
+    /// use light_tool::data_uri::DataUri;
+    /// let uri = DataUri::from_file("/opt/light-tool/chart.png").unwrap();
+    /// println!("<img src=\"{}\">", uri)
+    /// ```
+    pub fn from_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let data = fs::read(path.as_ref())?;
+        Ok(DataUri::new(mime_type(path, &data), data))
+    }
+
+    /// 解析 `data:[<mediatype>][;base64],<data>`
+    ///
+    /// 省略媒体类型时按 RFC 2397 补全为 `text/plain;charset=US-ASCII`，补全的参数会写入 `parameters`，
+    /// 因此 `to_string()` 不会原样还原输入（`data:,x` 输出为 `data:text/plain;charset=US-ASCII,x`）
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let input = input.trim();
+        let rest = input
+            .get(..5)
+            .filter(|scheme| scheme.eq_ignore_ascii_case("data:"))
+            .map(|_| &input[5..])
+            .ok_or("Invalid data URI: missing data: scheme")?;
+        let (meta, data) = rest.split_once(',').ok_or("Invalid data URI: missing comma")?;
+
+        let mut parts: Vec<&str> = meta.split(';').collect();
+        let base64 = parts.len() > 1 && parts[parts.len() - 1].trim().eq_ignore_ascii_case("base64");
+        if base64 {
+            parts.pop();
+        }
+
+        let mime_type = parts[0].trim().to_ascii_lowercase();
+        let mut parameters = Vec::new();
+        for parameter in &parts[1..] {
+            let (name, value) = parameter
+                .split_once('=')
+                .ok_or_else(|| format!("Invalid data URI parameter: {}", parameter))?;
+            parameters.push((name.trim().to_ascii_lowercase(), percent::decode(value.trim())?));
+        }
+        // 省略媒体类型时默认为 text/plain;charset=US-ASCII
+        let mime_type = if mime_type.is_empty() {
+            if parameters.is_empty() {
+                parameters.push(("charset".to_string(), "US-ASCII".to_string()));
+            }
+            DEFAULT_MIME_TYPE.to_string()
+        } else if mime_type.contains('/') {
+            mime_type
+        } else {
+            return Err(format!("Invalid data URI media type: {}", mime_type));
+        };
+
+        let mut data = percent::decode_bytes(data).map_err(|e| e.to_string())?;
+        if base64 {
+            let encoded = String::from_utf8(data).map_err(|e| e.to_string())?;
+            data = base64::STANDARD.decode_with(&encoded, DecodeMode::Lenient).map_err(|e| e.to_string())?;
+        }
+
+        Ok(DataUri { mime_type, parameters, base64, data })
+    }
+
+    /// 获取参数值，参数名不区分大小写
+    pub fn parameter(&self, name: &str) -> Option<&str> {
+        self.parameters
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+impl fmt::Display for DataUri {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "data:{}", self.mime_type)?;
+        for (name, value) in &self.parameters {
+            write!(f, ";{}={}", name, percent::encode(value, &EncodeSet::COMPONENT))?;
+        }
+        if self.base64 {
+            write!(f, ";base64,{}", base64::encode_bytes(&self.data))
+        } else {
+            write!(f, ",{}", percent::encode(&self.data, &DATA))
+        }
+    }
+}
+
+/// 检测文件的媒体类型，优先根据已知的扩展名判断，其次根据文件头，无法识别时为 `application/octet-stream`
+///
+/// # Example
+/// ```rust
+/// use light_tool::data_uri;
+/// assert_eq!(data_uri::mime_type("chart.bin", b"\x89PNG\r\n\x1a\n"), "image/png");
+/// assert_eq!(data_uri::mime_type("logo.SVG", b"<svg/>"), "image/svg+xml");
+/// assert_eq!(data_uri::mime_type("cars.csv", b"BMW,Audi\n"), "text/csv");
+/// assert_eq!(data_uri::mime_type("unknown", b""), "application/octet-stream")
+/// ```
+pub fn mime_type<P: AsRef<Path>>(path: P, data: &[u8]) -> &'static str {
+    path.as_ref()
+        .extension()
+        .and_then(|extension| from_extension(&extension.to_str()?.to_ascii_lowercase()))
+        .or_else(|| sniff(data))
+        .unwrap_or("application/octet-stream")
+}
+
+/// 根据文件头（魔数）识别常见格式
+fn sniff(data: &[u8]) -> Option<&'static str> {
+    const SIGNATURES: &[(&[u8], &str)] = &[
+        (b"\x89PNG\r\n\x1a\n", "image/png"),
+        (b"\xff\xd8\xff", "image/jpeg"),
+        (b"GIF87a", "image/gif"),
+        (b"GIF89a", "image/gif"),
+        (b"\x00\x00\x01\x00", "image/x-icon"),
+        (b"%PDF-", "application/pdf"),
+        (b"\x1f\x8b", "application/gzip"),
+        (b"wOFF", "font/woff"),
+        (b"wOF2", "font/woff2"),
+        (b"ID3", "audio/mpeg"),
+        (b"OggS", "audio/ogg"),
+    ];
+    if let Some(&(_, mime)) = SIGNATURES.iter().find(|(signature, _)| data.starts_with(signature)) {
+        return Some(mime);
+    }
+    // BMP 只有 2 字节魔数，还需检查偏移 14 处的 DIB 头长度，避免误判以 `BM` 开头的文本
+    if let (Some(b"BM"), Some(size)) = (data.get(..2), data.get(14..18)) {
+        if matches!(u32::from_le_bytes(size.try_into().unwrap()), 12 | 40 | 52 | 56 | 108 | 124) {
+            return Some("image/bmp");
+        }
+    }
+    // RIFF 容器：RIFF <长度> <类型>
+    match (data.get(..4), data.get(8..12)) {
+        (Some(b"RIFF"), Some(b"WEBP")) => Some("image/webp"),
+        (Some(b"RIFF"), Some(b"WAVE")) => Some("audio/wav"),
+        _ => None,
+    }
+}
+
+fn from_extension(extension: &str) -> Option<&'static str> {
+    let mime = match extension {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "bmp" => "image/bmp",
+        "ico" => "image/x-icon",
+        "svg" => "image/svg+xml",
+        "pdf" => "application/pdf",
+        "json" => "application/json",
+        "js" => "text/javascript",
+        "css" => "text/css",
+        "html" | "htm" => "text/html",
+        "csv" => "text/csv",
+        "txt" => "text/plain",
+        "xml" => "application/xml",
+        "woff" => "font/woff",
+        "woff2" => "font/woff2",
+        "ttf" => "font/ttf",
+        "mp3" => "audio/mpeg",
+        "wav" => "audio/wav",
+        "ogg" => "audio/ogg",
+        "mp4" => "video/mp4",
+        "gz" => "application/gzip",
+        _ => return None,
+    };
+    Some(mime)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let png = b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR".to_vec();
+        let uri = DataUri::new("image/png", png.clone());
+        assert_eq!(uri.to_string(), "data:image/png;base64,iVBORw0KGgoAAAANSUhEUg==");
+        assert_eq!(DataUri::parse(&uri.to_string()).unwrap(), uri);
+
+        let svg = DataUri::new("image/svg+xml", "<svg><text>50% #1</text></svg>").with_parameter("charset", "utf-8").percent_encoded();
+        assert_eq!(svg.to_string(), "data:image/svg+xml;charset=utf-8,%3Csvg%3E%3Ctext%3E50%25%20%231%3C/text%3E%3C/svg%3E");
+        assert_eq!(DataUri::parse(&svg.to_string()).unwrap(), svg);
+    }
+
+    #[test]
+    fn test_parse() {
+        let uri = DataUri::parse("data:,A%20brief%20note").unwrap();
+        assert_eq!(uri.mime_type, "text/plain");
+        assert_eq!(uri.parameter("CHARSET"), Some("US-ASCII"));
+        assert!(!uri.base64);
+        assert_eq!(uri.data, b"A brief note");
+        // RFC 2397 的默认值会被补全
+        assert_eq!(DataUri::parse("data:,x").unwrap().to_string(), "data:text/plain;charset=US-ASCII,x");
+
+        let uri = DataUri::parse("DATA:Text/HTML;Charset=utf-8;BASE64,PGI+\r\nSGk8L2I+").unwrap();
+        assert_eq!(uri.mime_type, "text/html");
+        assert_eq!(uri.parameter("charset"), Some("utf-8"));
+        assert_eq!(uri.data, b"<b>Hi</b>");
+
+        assert!(DataUri::parse("http://example.com").is_err());
+        assert!(DataUri::parse("data:text/plain").is_err());
+        assert!(DataUri::parse("data:text;base64,SGk=").is_err());
+        assert!(DataUri::parse("data:;base64,S!k=").is_err());
+    }
+
+    #[test]
+    fn test_from_file() {
+        let dir = std::env::temp_dir().join(format!("light-tool-data-uri-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let gif = dir.join("pixel.dat");
+        fs::write(&gif, b"GIF89a\x01\x00\x01\x00").unwrap();
+        let css = dir.join("style.css");
+        fs::write(&css, "body{}").unwrap();
+
+        assert_eq!(DataUri::from_file(&gif).unwrap().to_string(), "data:image/gif;base64,R0lGODlhAQABAA==");
+        assert_eq!(DataUri::from_file(&css).unwrap().mime_type, "text/css");
+        assert!(DataUri::from_file(dir.join("missing.png")).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_mime_type() {
+        // 扩展名与文件头不一致时以已知的扩展名为准
+        assert_eq!(mime_type("cars.csv", b"BMW,Audi\n"), "text/csv");
+        assert_eq!(mime_type("photo.jpg", b"\x89PNG\r\n\x1a\n"), "image/jpeg");
+        assert_eq!(mime_type("notes.txt", b"GIF89a is a format"), "text/plain");
+
+        // 未知扩展名时根据文件头判断，BMP 需要合法的 DIB 头
+        let mut bmp = b"BM\x46\x00\x00\x00\x00\x00\x00\x00\x36\x00\x00\x00".to_vec();
+        bmp.extend_from_slice(&40u32.to_le_bytes());
+        assert_eq!(mime_type("image", &bmp), "image/bmp");
+        assert_eq!(mime_type("cars", b"BMW,Audi,Mercedes,Porsche\n"), "application/octet-stream");
+        assert_eq!(mime_type("archive.bin", b"\x1f\x8b\x08"), "application/gzip");
+    }
+}
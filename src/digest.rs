@@ -0,0 +1,227 @@
+use crate::{base64, hex};
+
+/// 哈希算法的通用接口
+///
+/// # Example
+/// ```rust
+/// use light_tool::digest::Digest;
+/// use light_tool::hmac::Sha256;
+/// use light_tool::md5::Md5;
+///
+/// fn checksum<D: Digest>(data: &[u8]) -> String {
+///     D::hex_digest(data)
+/// }
+/// println!("md5: {}", checksum::<Md5>(b"Hello, world!"));
+/// println!("sha256: {}", checksum::<Sha256>(b"Hello, world!"));
+/// ```
+pub trait Digest: Sized + Clone {
+    /// 哈希值类型
+    type Output: AsRef<[u8]> + Copy;
+
+    /// 初始化哈希状态
+    fn new() -> Self;
+
+    /// 写入数据
+    fn update(&mut self, input: &[u8]);
+
+    /// 计算最终的哈希值
+    fn finalize(self) -> Self::Output;
+
+    /// 哈希值长度（字节）
+    fn output_size() -> usize;
+
+    /// 分组长度（字节）
+    fn block_size() -> usize;
+
+    /// 计算输入数据的哈希值
+    fn digest<T: AsRef<[u8]>>(input: T) -> Self::Output {
+        let mut hasher = Self::new();
+        hasher.update(input.as_ref());
+        hasher.finalize()
+    }
+
+    /// 计算输入数据的哈希值，返回十六进制字符串
+    fn hex_digest<T: AsRef<[u8]>>(input: T) -> String {
+        hex::encode(Self::digest(input))
+    }
+
+    /// 计算输入数据的哈希值，返回 Base64 字符串
+    fn base64_digest<T: AsRef<[u8]>>(input: T) -> String {
+        base64::encode_bytes(Self::digest(input).as_ref())
+    }
+
+    /// 计算最终的哈希值，返回十六进制字符串
+    fn finalize_hex(self) -> String {
+        hex::encode(self.finalize())
+    }
+
+    /// 计算最终的哈希值，返回 Base64 字符串
+    fn finalize_base64(self) -> String {
+        base64::encode_bytes(self.finalize().as_ref())
+    }
+}
+
+/// 分组缓冲区，凑满 `N` 字节即交给压缩函数处理
+#[derive(Clone)]
+pub(crate) struct BlockBuffer<const N: usize> {
+    buffer: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> BlockBuffer<N> {
+    pub(crate) fn new() -> Self {
+        Self { buffer: [0u8; N], len: 0 }
+    }
+
+    /// 写入数据，完整的分组直接从输入中处理，不经过缓冲区
+    pub(crate) fn update(&mut self, mut input: &[u8], mut compress: impl FnMut(&[u8; N])) {
+        if self.len > 0 {
+            let take = (N - self.len).min(input.len());
+            self.buffer[self.len..self.len + take].copy_from_slice(&input[..take]);
+            self.len += take;
+            input = &input[take..];
+            if self.len < N {
+                return;
+            }
+            compress(&self.buffer);
+            self.len = 0;
+        }
+
+        let mut blocks = input.chunks_exact(N);
+        for block in &mut blocks {
+            compress(block.try_into().unwrap());
+        }
+
+        let rest = blocks.remainder();
+        self.buffer[..rest.len()].copy_from_slice(rest);
+        self.len = rest.len();
+    }
+
+    /// MD 结构的填充：附加 1 位 + 填充 0 + 附加消息长度 `length`
+    pub(crate) fn pad(&mut self, length: &[u8], mut compress: impl FnMut(&[u8; N])) {
+        self.buffer[self.len] = 0x80;
+        self.len += 1;
+        if self.len > N - length.len() {
+            self.buffer[self.len..].fill(0);
+            compress(&self.buffer);
+            self.len = 0;
+        }
+        self.buffer[self.len..N - length.len()].fill(0);
+        self.buffer[N - length.len()..].copy_from_slice(length);
+        compress(&self.buffer);
+        self.len = 0;
+    }
+}
+
+/// 为哈希结构体实现 `Digest` 与 `std::io::Write`，`$output` 为哈希值长度
+macro_rules! impl_digest {
+    ($hasher:ty, $output:expr, $block:expr) => {
+        impl $crate::digest::Digest for $hasher {
+            type Output = [u8; $output];
+
+            fn new() -> Self {
+                <$hasher>::new()
+            }
+
+            fn update(&mut self, input: &[u8]) {
+                <$hasher>::update(self, input)
+            }
+
+            fn finalize(self) -> Self::Output {
+                <$hasher>::finalize(self)
+            }
+
+            fn output_size() -> usize {
+                $output
+            }
+
+            fn block_size() -> usize {
+                $block
+            }
+        }
+
+        impl std::io::Write for $hasher {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                <$hasher>::update(self, buf);
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+    };
+}
+
+pub(crate) use impl_digest;
+
+/// 测试数据：0 ~ 250 循环，周期与分组长度互质
+#[cfg(test)]
+pub(crate) fn test_data(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i % 251) as u8).collect()
+}
+
+/// 以每种分块大小逐块写入，结果必须与一次性写入一致
+#[cfg(test)]
+pub(crate) fn assert_chunked<H, O>(
+    new: impl Fn() -> H,
+    update: impl Fn(&mut H, &[u8]),
+    finalize: impl Fn(H) -> O,
+    data: &[u8],
+    chunk_sizes: &[usize],
+) where
+    O: PartialEq + std::fmt::Debug,
+{
+    let mut hasher = new();
+    update(&mut hasher, data);
+    let expected = finalize(hasher);
+    for &chunk_size in chunk_sizes {
+        let mut hasher = new();
+        for chunk in data.chunks(chunk_size) {
+            update(&mut hasher, chunk);
+        }
+        assert_eq!(finalize(hasher), expected, "len: {}, chunk: {}", data.len(), chunk_size);
+    }
+}
+
+/// 在分组长度 B 的边界附近（B - 1、B、B + 1 等）检查分块写入
+#[cfg(test)]
+pub(crate) fn assert_streaming<D: Digest>()
+where
+    D::Output: PartialEq + std::fmt::Debug,
+{
+    let block = D::block_size();
+    let chunk_sizes = [1, 7, block - 1, block, block + 1, 3 * block + 5];
+    for len in [0, block - 1, block, block + 1, 2 * block, 1000] {
+        assert_chunked(D::new, D::update, D::finalize, &test_data(len), &chunk_sizes);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+    use super::*;
+    use crate::hmac::Sha256;
+    use crate::md5::Md5;
+
+    #[test]
+    fn test_digest() {
+        assert_eq!(Md5::hex_digest("hello world"), "5eb63bbbe01eeed093cb22bb8f5acdc3");
+        assert_eq!(
+            Sha256::hex_digest("Hello, world!"),
+            "315f5bdb76d078c43b8ac0064e4a0164612b1fce77c869345bfc94c75894edd3"
+        );
+        assert_eq!(Md5::base64_digest(""), "1B2M2Y8AsgTpgAmY7PhCfg==");
+        assert_eq!(<Md5 as Digest>::output_size(), 16);
+        assert_eq!(<Sha256 as Digest>::output_size(), 32);
+        assert_eq!(<Sha256 as Digest>::block_size(), 64);
+    }
+
+    #[test]
+    fn test_io_copy() {
+        let data = vec![0x61u8; 10000];
+        let mut sha256 = Sha256::new();
+        io::copy(&mut data.as_slice(), &mut sha256).unwrap();
+        assert_eq!(sha256.finalize(), Sha256::digest(&data));
+    }
+}
@@ -0,0 +1,392 @@
+use std::io;
+use crate::curve25519::{self, impl_key_encoding, Fe, ONE, ZERO};
+use crate::digest::Digest;
+use crate::hmac::Sha512;
+use crate::{ct, random};
+
+/// 曲线参数 d = -121665 / 121666
+const D: Fe = Fe([0x78a3, 0x1359, 0x4dca, 0x75eb, 0xd8ab, 0x4141, 0x0a4d, 0x0070, 0xe898, 0x7779, 0x4079, 0x8cc7, 0xfe73, 0x2b6f, 0x6cee, 0x5203]);
+/// 2 * d
+const D2: Fe = Fe([0xf159, 0x26b2, 0x9b94, 0xebd6, 0xb156, 0x8283, 0x149a, 0x00e0, 0xd130, 0xeef3, 0x80f2, 0x198e, 0xfce7, 0x56df, 0xd9dc, 0x2406]);
+/// 基点 x 坐标
+const BASE_X: Fe = Fe([0xd51a, 0x8f25, 0x2d60, 0xc956, 0xa7b2, 0x9525, 0xc760, 0x692c, 0xdc5c, 0xfdd6, 0xe231, 0xc0a4, 0x53fe, 0xcd6e, 0x36d3, 0x2169]);
+/// 基点 y 坐标（4/5）
+const BASE_Y: Fe = Fe([0x6658, 0x6666, 0x6666, 0x6666, 0x6666, 0x6666, 0x6666, 0x6666, 0x6666, 0x6666, 0x6666, 0x6666, 0x6666, 0x6666, 0x6666, 0x6666]);
+/// sqrt(-1)
+const SQRT_M1: Fe = Fe([0xa0b0, 0x4a0e, 0x1b27, 0xc4ee, 0xe478, 0xad2f, 0x1806, 0x2f43, 0xd7a7, 0x3dfb, 0x0099, 0x2b4d, 0xdf0b, 0x4fc1, 0x2480, 0x2b83]);
+/// 群的阶 L = 2^252 + 27742317777372353535851937790883648493（小端）
+const L: [i64; 32] = [
+    0xed, 0xd3, 0xf5, 0x5c, 0x1a, 0x63, 0x12, 0x58, 0xd6, 0x9c, 0xf7, 0xa2, 0xde, 0xf9, 0xde, 0x14,
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x10,
+];
+
+/// 签名长度（字节）
+pub const SIGNATURE_LEN: usize = 64;
+
+/// 扩展坐标 (X, Y, Z, T) 表示的曲线点
+#[derive(Clone, Copy)]
+struct Point {
+    x: Fe,
+    y: Fe,
+    z: Fe,
+    t: Fe,
+}
+
+impl Point {
+    const IDENTITY: Point = Point { x: ZERO, y: ONE, z: ONE, t: ZERO };
+
+    fn base() -> Point {
+        Point { x: BASE_X, y: BASE_Y, z: ONE, t: BASE_X * BASE_Y }
+    }
+
+    /// 统一加法公式，同样适用于倍点
+    fn add(self, q: Point) -> Point {
+        let a = (self.y - self.x) * (q.y - q.x);
+        let b = (self.x + self.y) * (q.x + q.y);
+        let c = self.t * q.t * D2;
+        let d = self.z * q.z;
+        let d = d + d;
+        let (e, f, g, h) = (b - a, d - c, d + c, b + a);
+        Point { x: e * f, y: h * g, z: g * f, t: e * h }
+    }
+
+    fn swap(p: &mut Point, q: &mut Point, choice: i64) {
+        Fe::swap(&mut p.x, &mut q.x, choice);
+        Fe::swap(&mut p.y, &mut q.y, choice);
+        Fe::swap(&mut p.z, &mut q.z, choice);
+        Fe::swap(&mut p.t, &mut q.t, choice);
+    }
+
+    /// 标量乘法，每一位都执行相同的运算
+    fn mul(self, scalar: &[u8; 32]) -> Point {
+        let mut p = Point::IDENTITY;
+        let mut q = self;
+        for i in (0..256).rev() {
+            let bit = ((scalar[i / 8] >> (i & 7)) & 1) as i64;
+            Point::swap(&mut p, &mut q, bit);
+            q = q.add(p);
+            p = p.add(p);
+            Point::swap(&mut p, &mut q, bit);
+        }
+        p
+    }
+
+    fn encode(self) -> [u8; 32] {
+        let zi = self.z.invert();
+        let mut bytes = (self.y * zi).to_bytes();
+        bytes[31] ^= (self.x * zi).is_negative() << 7;
+        bytes
+    }
+
+    /// 解码并取负，用于验签时计算 [S]B - [k]A；y 不小于 p 或不是曲线上的点时返回 None
+    fn decode_neg(bytes: &[u8; 32]) -> Option<Point> {
+        let y = Fe::from_bytes(bytes);
+        // 拒绝非规范编码，否则同一个公钥存在多种编码
+        let mut canonical = *bytes;
+        canonical[31] &= 0x7f;
+        if y.to_bytes() != canonical {
+            return None;
+        }
+        let y2 = y.square();
+        let num = y2 - ONE;
+        let den = y2 * D + ONE;
+
+        // x = sqrt(num / den) = num * den^3 * (num * den^7)^((p - 5) / 8)
+        let den2 = den.square();
+        let den3 = den2 * den;
+        let mut x = (den2.square() * den3 * num).pow_p58() * num * den3;
+        if x.square() * den != num {
+            x = x * SQRT_M1;
+        }
+        if x.square() * den != num {
+            return None;
+        }
+        if x.is_negative() == bytes[31] >> 7 {
+            x = -x;
+        }
+        Some(Point { x, y, z: ONE, t: x * y })
+    }
+}
+
+/// 对 64 个 8 位分段表示的整数取模 L
+fn mod_l(x: &mut [i64; 64]) -> [u8; 32] {
+    for i in (32..64).rev() {
+        let mut carry = 0;
+        let mut j = i - 32;
+        while j < i - 12 {
+            x[j] += carry - 16 * x[i] * L[j - (i - 32)];
+            carry = (x[j] + 128) >> 8;
+            x[j] -= carry << 8;
+            j += 1;
+        }
+        x[j] += carry;
+        x[i] = 0;
+    }
+    let mut carry = 0;
+    for j in 0..32 {
+        x[j] += carry - (x[31] >> 4) * L[j];
+        carry = x[j] >> 8;
+        x[j] &= 255;
+    }
+    for j in 0..32 {
+        x[j] -= carry * L[j];
+    }
+    let mut r = [0u8; 32];
+    for i in 0..32 {
+        x[i + 1] += x[i] >> 8;
+        r[i] = (x[i] & 255) as u8;
+    }
+    r
+}
+
+/// SHA-512(parts...) mod L
+fn hash_to_scalar(parts: &[&[u8]]) -> [u8; 32] {
+    let mut hasher = Sha512::new();
+    for part in parts {
+        hasher.update(part);
+    }
+    let mut x = [0i64; 64];
+    for (limb, byte) in x.iter_mut().zip(hasher.finalize()) {
+        *limb = byte as i64;
+    }
+    mod_l(&mut x)
+}
+
+/// 判断标量是否小于 L（拒绝可延展的签名）
+fn is_canonical(s: &[u8]) -> bool {
+    for i in (0..32).rev() {
+        if (s[i] as i64) != L[i] {
+            return (s[i] as i64) < L[i];
+        }
+    }
+    false
+}
+
+/// Ed25519 私钥 (RFC 8032)
+///
+/// # Example
+/// ```rust
+/// use light_tool::ed25519::{SigningKey, VerifyingKey, Signature};
+/// let signing_key = SigningKey::generate().unwrap();
+/// let signature = signing_key.sign("manifest v1.2.3");
+///
+/// // 分发公钥与签名
+/// let verifying_key = VerifyingKey::from_base64(&signing_key.verifying_key().to_base64()).unwrap();
+/// let signature = Signature::from_hex(&signature.to_hex()).unwrap();
+/// assert!(verifying_key.verify("manifest v1.2.3", &signature));
+/// assert!(!verifying_key.verify("manifest v1.2.4", &signature))
+/// ```
+#[derive(Clone)]
+pub struct SigningKey {
+    seed: [u8; 32],
+    scalar: [u8; 32],
+    prefix: [u8; 32],
+    public: VerifyingKey,
+}
+
+impl SigningKey {
+    /// 使用安全随机数生成私钥
+    pub fn generate() -> io::Result<Self> {
+        let mut seed = [0u8; 32];
+        random::fill_secure(&mut seed)?;
+        Ok(Self::from_bytes(&seed))
+    }
+
+    /// 从 32 字节种子导入
+    pub fn from_bytes(seed: &[u8; 32]) -> Self {
+        let hash = Sha512::digest(seed);
+        let mut scalar: [u8; 32] = hash[..32].try_into().unwrap();
+        scalar[0] &= 248;
+        scalar[31] &= 127;
+        scalar[31] |= 64;
+        let public = VerifyingKey(Point::base().mul(&scalar).encode());
+        SigningKey { seed: *seed, scalar, prefix: hash[32..].try_into().unwrap(), public }
+    }
+
+    /// 从任意长度的切片导入，长度必须为 32
+    pub fn from_slice(bytes: &[u8]) -> Result<Self, String> {
+        Ok(Self::from_bytes(&curve25519::to_array(bytes, "Ed25519 seed")?))
+    }
+
+    /// 导出 32 字节种子
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.seed
+    }
+
+    /// 对应的公钥
+    pub fn verifying_key(&self) -> VerifyingKey {
+        self.public
+    }
+
+    /// 签名
+    pub fn sign<T: AsRef<[u8]>>(&self, message: T) -> Signature {
+        let message = message.as_ref();
+        let r = hash_to_scalar(&[&self.prefix, message]);
+        let big_r = Point::base().mul(&r).encode();
+        let k = hash_to_scalar(&[&big_r, &self.public.0, message]);
+
+        // S = r + k * s mod L
+        let mut x = [0i64; 64];
+        for i in 0..32 {
+            x[i] = r[i] as i64;
+        }
+        for i in 0..32 {
+            for j in 0..32 {
+                x[i + j] += k[i] as i64 * self.scalar[j] as i64;
+            }
+        }
+        let mut signature = [0u8; SIGNATURE_LEN];
+        signature[..32].copy_from_slice(&big_r);
+        signature[32..].copy_from_slice(&mod_l(&mut x));
+        Signature(signature)
+    }
+}
+
+impl_key_encoding!(SigningKey);
+
+/// Ed25519 公钥
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VerifyingKey([u8; 32]);
+
+impl VerifyingKey {
+    /// 从 32 字节导入，不是合法曲线点时返回错误
+    pub fn from_bytes(bytes: &[u8; 32]) -> Result<Self, String> {
+        match Point::decode_neg(bytes) {
+            Some(_) => Ok(VerifyingKey(*bytes)),
+            None => Err("Invalid Ed25519 public key".to_string()),
+        }
+    }
+
+    /// 从任意长度的切片导入，长度必须为 32
+    pub fn from_slice(bytes: &[u8]) -> Result<Self, String> {
+        Self::from_bytes(&curve25519::to_array(bytes, "Ed25519 public key")?)
+    }
+
+    /// 导出 32 字节
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.0
+    }
+
+    /// 验签
+    pub fn verify<T: AsRef<[u8]>>(&self, message: T, signature: &Signature) -> bool {
+        let (big_r, s) = signature.0.split_at(32);
+        if !is_canonical(s) {
+            return false;
+        }
+        let neg_a = match Point::decode_neg(&self.0) {
+            Some(point) => point,
+            None => return false,
+        };
+        let k = hash_to_scalar(&[big_r, &self.0, message.as_ref()]);
+
+        // [S]B - [k]A == R
+        let check = neg_a.mul(&k).add(Point::base().mul(s.try_into().unwrap()));
+        ct::eq(check.encode(), big_r)
+    }
+}
+
+impl_key_encoding!(VerifyingKey);
+
+/// Ed25519 签名
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Signature([u8; SIGNATURE_LEN]);
+
+impl Signature {
+    /// 从 64 字节导入
+    pub fn from_bytes(bytes: &[u8; SIGNATURE_LEN]) -> Self {
+        Signature(*bytes)
+    }
+
+    /// 从任意长度的切片导入，长度必须为 64
+    pub fn from_slice(bytes: &[u8]) -> Result<Self, String> {
+        Ok(Signature(curve25519::to_array(bytes, "Ed25519 signature")?))
+    }
+
+    /// 导出 64 字节
+    pub fn to_bytes(&self) -> [u8; SIGNATURE_LEN] {
+        self.0
+    }
+}
+
+impl_key_encoding!(Signature);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rfc8032() {
+        // RFC 8032 §7.1 TEST 1 ~ 3
+        let cases = [
+            (
+                "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60",
+                "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+                "",
+                "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b",
+            ),
+            (
+                "4ccd089b28ff96da9db6c346ec114e0f5b8a319f35aba624da8cf6ed4fb8a6fb",
+                "3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c",
+                "72",
+                "92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00",
+            ),
+            (
+                "c5aa8df43f9f837bedb7442f31dcb7b166d38535076f094b85ce3a2e0b4458f7",
+                "fc51cd8e6218a1a38da47ed00230f0580816ed13ba3303ac5deb911548908025",
+                "af82",
+                "6291d657deec24024827e69c3abe01a30ce548a284743a445e3680d7db5ac3ac18ff9b538d16f290ae67f760984dc6594a7c15e9716ed28dc027beceea1ec40a",
+            ),
+        ];
+        for (secret, public, message, signature) in cases {
+            let message = crate::hex::decode(message).unwrap();
+            let signing_key = SigningKey::from_hex(secret).unwrap();
+            assert_eq!(signing_key.verifying_key().to_hex(), public);
+            assert_eq!(signing_key.sign(&message).to_hex(), signature);
+
+            let verifying_key = VerifyingKey::from_hex(public).unwrap();
+            let signature = Signature::from_hex(signature).unwrap();
+            assert!(verifying_key.verify(&message, &signature));
+            assert!(!verifying_key.verify(b"tampered", &signature));
+        }
+    }
+
+    #[test]
+    fn test_reject_invalid() {
+        let signing_key = SigningKey::from_bytes(&[7; 32]);
+        let verifying_key = signing_key.verifying_key();
+        let signature = signing_key.sign("message");
+
+        // S + L 与 S 等价，但必须被拒绝
+        let mut malleable = signature.to_bytes();
+        let mut carry = 0;
+        for i in 0..32 {
+            let sum = malleable[32 + i] as i64 + L[i] + carry;
+            malleable[32 + i] = sum as u8;
+            carry = sum >> 8;
+        }
+        assert!(!verifying_key.verify("message", &Signature::from_bytes(&malleable)));
+
+        let mut flipped = signature.to_bytes();
+        flipped[0] ^= 1;
+        assert!(!verifying_key.verify("message", &Signature::from_bytes(&flipped)));
+
+        // y = 2 不在曲线上
+        let mut invalid = [0u8; 32];
+        invalid[0] = 2;
+        assert!(VerifyingKey::from_bytes(&invalid).is_err());
+        assert!(VerifyingKey::from_slice(&[0; 31]).is_err());
+
+        // y = p + 1 与 y = 1 是同一个点，非规范编码必须被拒绝
+        let mut one = [0u8; 32];
+        one[0] = 1;
+        assert!(VerifyingKey::from_bytes(&one).is_ok());
+        let mut non_canonical = [0xff; 32];
+        non_canonical[0] = 0xee;
+        non_canonical[31] = 0x7f;
+        assert!(VerifyingKey::from_bytes(&non_canonical).is_err());
+
+        assert_eq!(Signature::from_slice(&[0; 32]).unwrap_err(), "Invalid Ed25519 signature length: expected 64 bytes, got 32");
+        assert!(Signature::from_hex("zz").is_err());
+        assert_eq!(SigningKey::from_base64(&signing_key.to_base64()).unwrap().to_bytes(), [7; 32]);
+    }
+}
@@ -1,9 +1,15 @@
-use std::{fs, io};
-use std::fs::OpenOptions;
+use std::{fs, io, thread};
+use std::fs::{File, OpenOptions};
 use std::path::Path;
 use std::io::Error;
-use std::io::ErrorKind::NotFound;
-use std::io::Write;
+use std::io::ErrorKind::{InvalidData, NotFound};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use crate::{ct, hex};
+use crate::digest::Digest;
+
+/// 计算文件哈希时每次读取的字节数
+const HASH_BUFFER_SIZE: usize = 64 * 1024;
 
 /// FileCopy: 拷贝文件
 ///
@@ -61,7 +67,7 @@ pub fn rename(src_file: &str, dst_file: &str) -> io::Result<()> {
 pub fn append(file_path: &str, content: &str) -> io::Result<()> {
     create_parent_dir(file_path)?;
     let mut file = OpenOptions::new()
-        .write(true).append(true).create(true).open(file_path)?;
+        .append(true).create(true).open(file_path)?;
 
     write!(file, "{}", content)?;
 
@@ -86,6 +92,174 @@ pub fn create_parent_dir(dst_file: &str) -> io::Result<()> {
     Ok(())
 }
 
+/// FileHash: 分块读取文件并计算哈希值，内存占用与文件大小无关
+///
+/// # Example
+/// ```txt
+/// use light_tool::file;
+/// use light_tool::hmac::Sha256;
+/// println!("sha256: {:?}", file::hash::<Sha256>("/opt/light-tool/tt.txt").unwrap())
+/// ```
+pub fn hash<D: Digest>(file_path: &str) -> io::Result<D::Output> {
+    let mut file = File::open(file_path)?;
+    let mut hasher = D::new();
+    let mut buffer = vec![0u8; HASH_BUFFER_SIZE];
+    loop {
+        let n = match file.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        hasher.update(&buffer[..n]);
+    }
+    Ok(hasher.finalize())
+}
+
+/// FileHashHex: 计算文件哈希值，返回十六进制字符串
+///
+/// # Example
+/// ```txt
+/// use light_tool::file;
+/// use light_tool::md5::Md5;
+/// println!("md5: {}", file::hash_hex::<Md5>("/opt/light-tool/tt.txt").unwrap())
+/// ```
+pub fn hash_hex<D: Digest>(file_path: &str) -> io::Result<String> {
+    Ok(hex::encode(hash::<D>(file_path)?))
+}
+
+/// FileHashMany: 使用 `threads` 个线程并行计算多个文件的哈希值，结果与输入顺序一致
+///
+/// # Example
+/// ```txt
+/// use light_tool::file;
+/// use light_tool::hmac::Sha256;
+/// for (path, result) in file::hash_files::<Sha256>(&["/opt/light-tool/a.txt", "/opt/light-tool/b.txt"], 4) {
+///     println!("{}: {:?}", path, result)
+/// }
+/// ```
+pub fn hash_files<D: Digest>(file_paths: &[&str], threads: usize) -> Vec<(String, io::Result<String>)> {
+    let next = AtomicUsize::new(0);
+    let workers = threads.max(1).min(file_paths.len());
+
+    let mut results: Vec<(usize, io::Result<String>)> = thread::scope(|s| {
+        let handles: Vec<_> = (0..workers)
+            .map(|_| {
+                s.spawn(|| {
+                    let mut done = Vec::new();
+                    loop {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+                        if i >= file_paths.len() {
+                            break done;
+                        }
+                        done.push((i, hash_hex::<D>(file_paths[i])));
+                    }
+                })
+            })
+            .collect();
+        handles.into_iter().flat_map(|h| h.join().unwrap()).collect()
+    });
+
+    results.sort_by_key(|(i, _)| *i);
+    results.into_iter().map(|(i, result)| (file_paths[i].to_string(), result)).collect()
+}
+
+/// WriteChecksums: 生成与 coreutils（md5sum、sha256sum）输出格式一致的校验文件
+///
+/// 每行格式为 `哈希值  文件路径`；路径含 `\`、换行或回车时按 coreutils 规则转义，并在行首加 `\`
+///
+/// # Example
+/// ```txt
+/// use light_tool::file;
+/// use light_tool::hmac::Sha256;
+/// file::write_checksums::<Sha256>(&["/opt/light-tool/tt.txt"], "/opt/light-tool/tt.sha256sum", 4).unwrap()
+/// ```
+pub fn write_checksums<D: Digest>(file_paths: &[&str], checksum_file: &str, threads: usize) -> io::Result<()> {
+    let mut content = String::new();
+    for (path, result) in hash_files::<D>(file_paths, threads) {
+        let hash = result?;
+        match escape_path(&path) {
+            Some(escaped) => content.push_str(&format!("\\{}  {}\n", hash, escaped)),
+            None => content.push_str(&format!("{}  {}\n", hash, path)),
+        }
+    }
+    create_parent_dir(checksum_file)?;
+    fs::write(checksum_file, content)
+}
+
+/// VerifyChecksums: 校验 coreutils 格式的校验文件（同 `sha256sum -c`）
+///
+/// 返回每个文件的校验结果，文件无法读取时为 `Err`；支持文本模式 `哈希值  路径` 与二进制模式 `哈希值 *路径`
+///
+/// # Example
+/// ```txt
+/// use light_tool::file;
+/// use light_tool::hmac::Sha256;
+/// for (path, result) in file::verify_checksums::<Sha256>("/opt/light-tool/tt.sha256sum", 4).unwrap() {
+///     println!("{}: {}", path, if matches!(result, Ok(true)) { "OK" } else { "FAILED" })
+/// }
+/// ```
+pub fn verify_checksums<D: Digest>(checksum_file: &str, threads: usize) -> io::Result<Vec<(String, io::Result<bool>)>> {
+    let mut expected = Vec::new();
+    for line in BufReader::new(File::open(checksum_file)?).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        // 行首的 `\` 表示路径经过转义
+        let (escaped, line) = match line.strip_prefix('\\') {
+            Some(rest) => (true, rest),
+            None => (false, line.as_str()),
+        };
+        let (hash, path) = line
+            .split_once(' ')
+            .filter(|(hash, _)| hash.len() == D::output_size() * 2)
+            .map(|(hash, rest)| (hash.to_ascii_lowercase(), rest.strip_prefix([' ', '*']).unwrap_or(rest).to_string()))
+            .ok_or_else(|| Error::new(InvalidData, format!("校验文件格式不正确: {}", line)))?;
+        let path = if escaped {
+            unescape_path(&path).ok_or_else(|| Error::new(InvalidData, format!("校验文件格式不正确: {}", line)))?
+        } else {
+            path
+        };
+        expected.push((hash, path));
+    }
+
+    let paths: Vec<&str> = expected.iter().map(|(_, path)| path.as_str()).collect();
+    let results = hash_files::<D>(&paths, threads)
+        .into_iter()
+        .zip(&expected)
+        .map(|((path, result), (hash, _))| (path, result.map(|actual| ct::eq(actual, hash))))
+        .collect();
+    Ok(results)
+}
+
+/// 按 coreutils 规则转义路径中的 `\`、换行与回车，无需转义时返回 `None`
+fn escape_path(path: &str) -> Option<String> {
+    if !path.contains(['\\', '\n', '\r']) {
+        return None;
+    }
+    Some(path.replace('\\', "\\\\").replace('\n', "\\n").replace('\r', "\\r"))
+}
+
+/// 还原 `escape_path` 转义的路径，含有未知的转义序列时返回 `None`
+fn unescape_path(path: &str) -> Option<String> {
+    let mut output = String::with_capacity(path.len());
+    let mut chars = path.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            output.push(c);
+            continue;
+        }
+        match chars.next()? {
+            '\\' => output.push('\\'),
+            'n' => output.push('\n'),
+            'r' => output.push('\r'),
+            _ => return None,
+        }
+    }
+    Some(output)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::random;
@@ -118,4 +292,86 @@ mod tests {
             println!("create parent dir error: {}", e);
         }
     }
+
+    fn temp_path(name: &str) -> String {
+        std::env::temp_dir()
+            .join(format!("light-tool-{}-{}", std::process::id(), name))
+            .to_string_lossy()
+            .to_string()
+    }
+
+    #[test]
+    fn test_hash() {
+        use crate::hmac::Sha256;
+        use crate::md5::Md5;
+
+        let path = temp_path("hash.bin");
+        let data = crate::digest::test_data(200_000);
+        fs::write(&path, &data).unwrap();
+
+        assert_eq!(hash::<Sha256>(&path).unwrap(), Sha256::digest(&data));
+        assert_eq!(hash_hex::<Md5>(&path).unwrap(), crate::md5::str(&data));
+        assert!(hash::<Md5>(&temp_path("missing.bin")).is_err());
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_checksums() {
+        use crate::hmac::Sha256;
+
+        let paths: Vec<String> = (0..5).map(|i| temp_path(&format!("sum-{}.txt", i))).collect();
+        for (i, path) in paths.iter().enumerate() {
+            fs::write(path, format!("file {}", i)).unwrap();
+        }
+        let path_refs: Vec<&str> = paths.iter().map(|p| p.as_str()).collect();
+        let checksum_file = temp_path("files.sha256sum");
+
+        write_checksums::<Sha256>(&path_refs, &checksum_file, 3).unwrap();
+        let content = fs::read_to_string(&checksum_file).unwrap();
+        assert_eq!(
+            content.lines().next().unwrap(),
+            format!("{}  {}", crate::hmac::sha256("file 0"), paths[0])
+        );
+
+        fs::write(&paths[2], "changed").unwrap();
+        fs::remove_file(&paths[4]).unwrap();
+        let results = verify_checksums::<Sha256>(&checksum_file, 3).unwrap();
+        let ok: Vec<bool> = results.iter().map(|(_, r)| matches!(r, Ok(true))).collect();
+        assert_eq!(ok, vec![true, true, false, true, false]);
+        assert!(results[4].1.is_err());
+
+        for path in paths.iter().take(4) {
+            fs::remove_file(path).unwrap();
+        }
+        fs::remove_file(&checksum_file).unwrap();
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_checksums_escaped_path() {
+        use crate::hmac::Sha256;
+
+        // 与 `sha256sum` 一致：行首加 `\`，路径中的 `\` 与换行转义
+        let path = temp_path("a\\b\nc.txt");
+        fs::write(&path, "file").unwrap();
+        let checksum_file = temp_path("escaped.sha256sum");
+
+        write_checksums::<Sha256>(&[&path], &checksum_file, 1).unwrap();
+        let content = fs::read_to_string(&checksum_file).unwrap();
+        assert_eq!(content, format!("\\{}  {}\n", crate::hmac::sha256("file"), path.replace('\\', "\\\\").replace('\n', "\\n")));
+        assert_eq!(content.lines().count(), 1);
+
+        let results = verify_checksums::<Sha256>(&checksum_file, 1).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, path);
+        assert!(matches!(results[0].1, Ok(true)));
+
+        assert_eq!(unescape_path("a\\\\b\\rc"), Some("a\\b\rc".to_string()));
+        assert_eq!(unescape_path("a\\tb"), None);
+        assert_eq!(unescape_path("a\\"), None);
+
+        fs::remove_file(&path).unwrap();
+        fs::remove_file(&checksum_file).unwrap();
+    }
 }
@@ -0,0 +1,160 @@
+use std::fmt;
+pub use crate::base64::DecodeError;
+
+/// 每个字节对应的两个小写十六进制字符
+const LOWER: [[u8; 2]; 256] = pairs(b"0123456789abcdef");
+/// 每个字节对应的两个大写十六进制字符
+const UPPER: [[u8; 2]; 256] = pairs(b"0123456789ABCDEF");
+/// 字符到 4 位值的反查表，0xff 表示非法字符
+const VALUES: [u8; 256] = values();
+
+const fn pairs(digits: &[u8; 16]) -> [[u8; 2]; 256] {
+    let mut table = [[0u8; 2]; 256];
+    let mut i = 0;
+    while i < 256 {
+        table[i] = [digits[i >> 4], digits[i & 0xf]];
+        i += 1;
+    }
+    table
+}
+
+const fn values() -> [u8; 256] {
+    let mut table = [0xff; 256];
+    let mut i = 0;
+    while i < 10 {
+        table[b'0' as usize + i] = i as u8;
+        i += 1;
+    }
+    let mut i = 0;
+    while i < 6 {
+        table[b'a' as usize + i] = 10 + i as u8;
+        table[b'A' as usize + i] = 10 + i as u8;
+        i += 1;
+    }
+    table
+}
+
+/// 小写十六进制编码
+///
+/// # Example
+/// ```rust
+/// use light_tool::hex;
+/// assert_eq!(hex::encode([0xde, 0xad, 0xbe, 0xef]), "deadbeef")
+/// ```
+pub fn encode<T: AsRef<[u8]>>(input: T) -> String {
+    encode_with(input.as_ref(), &LOWER)
+}
+
+/// 大写十六进制编码
+///
+/// # Example
+/// ```rust
+/// use light_tool::hex;
+/// assert_eq!(hex::encode_upper("Hi"), "4869")
+/// ```
+pub fn encode_upper<T: AsRef<[u8]>>(input: T) -> String {
+    encode_with(input.as_ref(), &UPPER)
+}
+
+fn encode_with(bytes: &[u8], table: &[[u8; 2]; 256]) -> String {
+    let mut output = Vec::with_capacity(bytes.len() * 2);
+    for &byte in bytes {
+        output.extend_from_slice(&table[byte as usize]);
+    }
+    // 表中只有 ASCII 字符
+    String::from_utf8(output).unwrap()
+}
+
+/// 十六进制解码，不区分大小写
+///
+/// # Example
+/// ```rust
+/// use light_tool::hex::{self, DecodeError};
+/// assert_eq!(hex::decode("DEADbeef").unwrap(), [0xde, 0xad, 0xbe, 0xef]);
+/// assert_eq!(hex::decode("dead-eef"), Err(DecodeError::InvalidByte { offset: 4, byte: b'-' }));
+/// assert_eq!(hex::decode("abc"), Err(DecodeError::InvalidLength { length: 3 }))
+/// ```
+pub fn decode<T: AsRef<[u8]>>(input: T) -> Result<Vec<u8>, DecodeError> {
+    let input = input.as_ref();
+    if input.len() % 2 != 0 {
+        return Err(DecodeError::InvalidLength { length: input.len() });
+    }
+    let value = |offset: usize| match VALUES[input[offset] as usize] {
+        0xff => Err(DecodeError::InvalidByte { offset, byte: input[offset] }),
+        value => Ok(value),
+    };
+    (0..input.len()).step_by(2).map(|i| Ok(value(i)? << 4 | value(i + 1)?)).collect()
+}
+
+/// 以十六进制格式化字节，不产生中间 `String`
+///
+/// `{}` 与 `{:x}` 输出小写，`{:X}` 输出大写
+///
+/// # Example
+/// ```rust
+/// use light_tool::hex;
+/// let bytes = [0xca, 0xfe];
+/// assert_eq!(format!("{}", hex::display(&bytes)), "cafe");
+/// assert_eq!(format!("{:X}", hex::display(&bytes)), "CAFE")
+/// ```
+pub fn display<T: AsRef<[u8]> + ?Sized>(bytes: &T) -> Hex<'_> {
+    Hex(bytes.as_ref())
+}
+
+/// 见 `display`
+#[derive(Clone, Copy, Debug)]
+pub struct Hex<'a>(&'a [u8]);
+
+impl Hex<'_> {
+    fn write(&self, f: &mut fmt::Formatter<'_>, table: &[[u8; 2]; 256]) -> fmt::Result {
+        let mut buffer = [0u8; 128];
+        for chunk in self.0.chunks(buffer.len() / 2) {
+            for (i, &byte) in chunk.iter().enumerate() {
+                buffer[i * 2..i * 2 + 2].copy_from_slice(&table[byte as usize]);
+            }
+            // 表中只有 ASCII 字符
+            f.write_str(std::str::from_utf8(&buffer[..chunk.len() * 2]).unwrap())?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Hex<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write(f, &LOWER)
+    }
+}
+
+impl fmt::LowerHex for Hex<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write(f, &LOWER)
+    }
+}
+
+impl fmt::UpperHex for Hex<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write(f, &UPPER)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hex() {
+        let bytes: Vec<u8> = (0..=255).collect();
+        let lower = encode(&bytes);
+        let expected: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+        assert_eq!(lower, expected);
+        assert_eq!(encode_upper(&bytes), expected.to_uppercase());
+        assert_eq!(display(&bytes).to_string(), expected);
+        assert_eq!(format!("{:X}", display(&bytes)), expected.to_uppercase());
+        assert_eq!(decode(&lower).unwrap(), bytes);
+        assert_eq!(decode(expected.to_uppercase()).unwrap(), bytes);
+        assert_eq!(encode(""), "");
+        assert_eq!(decode("").unwrap(), []);
+        assert_eq!(decode("0g"), Err(DecodeError::InvalidByte { offset: 1, byte: b'g' }));
+        assert_eq!(decode("+f"), Err(DecodeError::InvalidByte { offset: 0, byte: b'+' }));
+    }
+}
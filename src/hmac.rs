@@ -1,4 +1,6 @@
-use std::fmt::Write;
+use crate::digest::{BlockBuffer, Digest};
+use crate::{ct, hex};
+use crate::md5::Md5;
 
 
 /// SHA-256 常量
@@ -15,113 +17,783 @@ const K: [u32; 64] = [
     0xc67178f2,
 ];
 
-/// 初始哈希值
+/// SHA-256 初始哈希值
 const INIT_HASH: [u32; 8] = [
     0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
 ];
 
-/// SHA-256 哈希函数
+/// SHA-224 初始哈希值
+const INIT_HASH_224: [u32; 8] = [
+    0xc1059ed8, 0x367cd507, 0x3070dd17, 0xf70e5939, 0xffc00b31, 0x68581511, 0x64f98fa7, 0xbefa4fa4,
+];
+
+/// SHA-512 常量
+const K512: [u64; 80] = [
+    0x428a2f98d728ae22, 0x7137449123ef65cd, 0xb5c0fbcfec4d3b2f, 0xe9b5dba58189dbbc,
+    0x3956c25bf348b538, 0x59f111f1b605d019, 0x923f82a4af194f9b, 0xab1c5ed5da6d8118,
+    0xd807aa98a3030242, 0x12835b0145706fbe, 0x243185be4ee4b28c, 0x550c7dc3d5ffb4e2,
+    0x72be5d74f27b896f, 0x80deb1fe3b1696b1, 0x9bdc06a725c71235, 0xc19bf174cf692694,
+    0xe49b69c19ef14ad2, 0xefbe4786384f25e3, 0x0fc19dc68b8cd5b5, 0x240ca1cc77ac9c65,
+    0x2de92c6f592b0275, 0x4a7484aa6ea6e483, 0x5cb0a9dcbd41fbd4, 0x76f988da831153b5,
+    0x983e5152ee66dfab, 0xa831c66d2db43210, 0xb00327c898fb213f, 0xbf597fc7beef0ee4,
+    0xc6e00bf33da88fc2, 0xd5a79147930aa725, 0x06ca6351e003826f, 0x142929670a0e6e70,
+    0x27b70a8546d22ffc, 0x2e1b21385c26c926, 0x4d2c6dfc5ac42aed, 0x53380d139d95b3df,
+    0x650a73548baf63de, 0x766a0abb3c77b2a8, 0x81c2c92e47edaee6, 0x92722c851482353b,
+    0xa2bfe8a14cf10364, 0xa81a664bbc423001, 0xc24b8b70d0f89791, 0xc76c51a30654be30,
+    0xd192e819d6ef5218, 0xd69906245565a910, 0xf40e35855771202a, 0x106aa07032bbd1b8,
+    0x19a4c116b8d2d0c8, 0x1e376c085141ab53, 0x2748774cdf8eeb99, 0x34b0bcb5e19b48a8,
+    0x391c0cb3c5c95a63, 0x4ed8aa4ae3418acb, 0x5b9cca4f7763e373, 0x682e6ff3d6b2b8a3,
+    0x748f82ee5defb2fc, 0x78a5636f43172f60, 0x84c87814a1f0ab72, 0x8cc702081a6439ec,
+    0x90befffa23631e28, 0xa4506cebde82bde9, 0xbef9a3f7b2c67915, 0xc67178f2e372532b,
+    0xca273eceea26619c, 0xd186b8c721c0c207, 0xeada7dd6cde0eb1e, 0xf57d4f7fee6ed178,
+    0x06f067aa72176fba, 0x0a637dc5a2c898a6, 0x113f9804bef90dae, 0x1b710b35131c471b,
+    0x28db77f523047d84, 0x32caab7b40c72493, 0x3c9ebe0a15c9bebc, 0x431d67c49c100d4c,
+    0x4cc5d4becb3e42b6, 0x597f299cfc657e2a, 0x5fcb6fab3ad6faec, 0x6c44198c4a475817,
+];
+
+/// SHA-512 初始哈希值
+const INIT_HASH_512: [u64; 8] = [
+    0x6a09e667f3bcc908, 0xbb67ae8584caa73b, 0x3c6ef372fe94f82b, 0xa54ff53a5f1d36f1,
+    0x510e527fade682d1, 0x9b05688c2b3e6c1f, 0x1f83d9abfb41bd6b, 0x5be0cd19137e2179,
+];
+
+/// SHA-384 初始哈希值
+const INIT_HASH_384: [u64; 8] = [
+    0xcbbb9d5dc1059ed8, 0x629a292a367cd507, 0x9159015a3070dd17, 0x152fecd8f70e5939,
+    0x67332667ffc00b31, 0x8eb44a8768581511, 0xdb0c2e0d64f98fa7, 0x47b5481dbefa4fa4,
+];
+
+/// SHA-1 初始哈希值
+const INIT_HASH_SHA1: [u32; 5] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0xc3d2e1f0];
+
+/// SHA-256 结构体
 ///
 /// # Example
 /// ```rust
-/// use light_tool::hmac;
-/// println!("sha256: {}", hmac::sha256("Hello, world!"))
+/// use light_tool::hmac::Sha256;
+/// let mut sha256 = Sha256::new();
+/// sha256.update(b"Hello, ");
+/// sha256.update(b"world!");
+/// println!("sha256: {:?}", sha256.finalize())
+/// ```
+#[derive(Clone)]
+pub struct Sha256 {
+    state: [u32; 8],            // 哈希状态
+    buffer: BlockBuffer<64>,    // 未满一个分组的数据
+    len: u64,                   // 消息总字节数
+}
+
+impl Default for Sha256 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Sha256 {
+    /// 初始化 SHA-256 状态
+    pub fn new() -> Self {
+        Self::with_state(INIT_HASH)
+    }
+
+    fn with_state(state: [u32; 8]) -> Self {
+        Self {
+            state,
+            buffer: BlockBuffer::new(),
+            len: 0,
+        }
+    }
+
+    /// 写入数据
+    pub fn update(&mut self, input: &[u8]) {
+        self.len = self.len.wrapping_add(input.len() as u64);
+        let state = &mut self.state;
+        self.buffer.update(input, |block| compress256(state, block));
+    }
+
+    /// 计算最终的哈希值
+    pub fn finalize(self) -> [u8; 32] {
+        let mut digest = [0u8; 32];
+        for (i, value) in self.finish().iter().enumerate() {
+            digest[i * 4..(i + 1) * 4].copy_from_slice(&value.to_be_bytes());
+        }
+        digest
+    }
+
+    fn finish(mut self) -> [u32; 8] {
+        let bit_len = self.len.wrapping_mul(8);
+        let state = &mut self.state;
+        self.buffer.pad(&bit_len.to_be_bytes(), |block| compress256(state, block));
+        self.state
+    }
+}
+
+crate::digest::impl_digest!(Sha256, 32, 64);
+
+/// SHA-224 结构体，即截断为 224 位、初始值不同的 SHA-256
+#[derive(Clone, Default)]
+pub struct Sha224 {
+    inner: Sha256,
+}
+
+impl Sha224 {
+    /// 初始化 SHA-224 状态
+    pub fn new() -> Self {
+        Self { inner: Sha256::with_state(INIT_HASH_224) }
+    }
+
+    /// 写入数据
+    pub fn update(&mut self, input: &[u8]) {
+        self.inner.update(input);
+    }
+
+    /// 计算最终的哈希值
+    pub fn finalize(self) -> [u8; 28] {
+        let mut digest = [0u8; 28];
+        for (i, value) in self.inner.finish().iter().take(7).enumerate() {
+            digest[i * 4..(i + 1) * 4].copy_from_slice(&value.to_be_bytes());
+        }
+        digest
+    }
+}
+
+crate::digest::impl_digest!(Sha224, 28, 64);
+
+/// SHA-512 结构体
+///
+/// # Example
+/// ```rust
+/// use light_tool::hmac::Sha512;
+/// let mut sha512 = Sha512::new();
+/// sha512.update(b"Hello, world!");
+/// println!("sha512: {:?}", sha512.finalize())
 /// ```
-pub fn sha256(input: &str) -> String {
-    let bytes = input.as_bytes();
-    let bit_len = bytes.len() * 8;
-    let mut data = bytes.to_vec();
-
-    // 填充数据：附加 1 位 + 填充 0 + 附加原始长度
-    data.push(0x80);
-    while (data.len() * 8) % 512 != 448 {
-        data.push(0x00);
-    }
-
-    for &byte in &(bit_len as u64).to_be_bytes() {
-        data.push(byte);
-    }
-
-    // 分块处理，每块 512 位（64 字节）
-    let mut hash = INIT_HASH;
-    for chunk in data.chunks(64) {
-        let mut w = [0u32; 64];
-        for i in 0..16 {
-            w[i] = u32::from_be_bytes([
-                chunk[i * 4],
-                chunk[i * 4 + 1],
-                chunk[i * 4 + 2],
-                chunk[i * 4 + 3],
-            ]);
+#[derive(Clone)]
+pub struct Sha512 {
+    state: [u64; 8],            // 哈希状态
+    buffer: BlockBuffer<128>,   // 未满一个分组的数据
+    len: u128,                  // 消息总字节数
+}
+
+impl Default for Sha512 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Sha512 {
+    /// 初始化 SHA-512 状态
+    pub fn new() -> Self {
+        Self::with_state(INIT_HASH_512)
+    }
+
+    fn with_state(state: [u64; 8]) -> Self {
+        Self {
+            state,
+            buffer: BlockBuffer::new(),
+            len: 0,
+        }
+    }
+
+    /// 写入数据
+    pub fn update(&mut self, input: &[u8]) {
+        self.len = self.len.wrapping_add(input.len() as u128);
+        let state = &mut self.state;
+        self.buffer.update(input, |block| compress512(state, block));
+    }
+
+    /// 计算最终的哈希值
+    pub fn finalize(self) -> [u8; 64] {
+        let mut digest = [0u8; 64];
+        for (i, value) in self.finish().iter().enumerate() {
+            digest[i * 8..(i + 1) * 8].copy_from_slice(&value.to_be_bytes());
         }
-        for i in 16..64 {
-            let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
-            let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
-            w[i] = w[i - 16]
-                .wrapping_add(s0)
-                .wrapping_add(w[i - 7])
-                .wrapping_add(s1);
+        digest
+    }
+
+    fn finish(mut self) -> [u64; 8] {
+        let bit_len = self.len.wrapping_mul(8);
+        let state = &mut self.state;
+        self.buffer.pad(&bit_len.to_be_bytes(), |block| compress512(state, block));
+        self.state
+    }
+}
+
+crate::digest::impl_digest!(Sha512, 64, 128);
+
+/// SHA-384 结构体，即截断为 384 位、初始值不同的 SHA-512
+#[derive(Clone, Default)]
+pub struct Sha384 {
+    inner: Sha512,
+}
+
+impl Sha384 {
+    /// 初始化 SHA-384 状态
+    pub fn new() -> Self {
+        Self { inner: Sha512::with_state(INIT_HASH_384) }
+    }
+
+    /// 写入数据
+    pub fn update(&mut self, input: &[u8]) {
+        self.inner.update(input);
+    }
+
+    /// 计算最终的哈希值
+    pub fn finalize(self) -> [u8; 48] {
+        let mut digest = [0u8; 48];
+        for (i, value) in self.inner.finish().iter().take(6).enumerate() {
+            digest[i * 8..(i + 1) * 8].copy_from_slice(&value.to_be_bytes());
         }
+        digest
+    }
+}
+
+crate::digest::impl_digest!(Sha384, 48, 128);
 
-        let mut a = hash[0];
-        let mut b = hash[1];
-        let mut c = hash[2];
-        let mut d = hash[3];
-        let mut e = hash[4];
-        let mut f = hash[5];
-        let mut g = hash[6];
-        let mut h = hash[7];
-
-        for i in 0..64 {
-            let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
-            let ch = (e & f) ^ (!e & g);
-            let temp1 = h
-                .wrapping_add(s1)
-                .wrapping_add(ch)
-                .wrapping_add(K[i])
-                .wrapping_add(w[i]);
-            let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
-            let maj = (a & b) ^ (a & c) ^ (b & c);
-            let temp2 = s0.wrapping_add(maj);
-
-            h = g;
-            g = f;
-            f = e;
-            e = d.wrapping_add(temp1);
-            d = c;
-            c = b;
-            b = a;
-            a = temp1.wrapping_add(temp2);
+/// SHA-1 结构体
+///
+/// SHA-1 已不具备抗碰撞性，仅用于兼容 WebSocket 握手等既有协议
+#[derive(Clone)]
+pub struct Sha1 {
+    state: [u32; 5],            // 哈希状态
+    buffer: BlockBuffer<64>,    // 未满一个分组的数据
+    len: u64,                   // 消息总字节数
+}
+
+impl Default for Sha1 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Sha1 {
+    /// 初始化 SHA-1 状态
+    pub fn new() -> Self {
+        Self {
+            state: INIT_HASH_SHA1,
+            buffer: BlockBuffer::new(),
+            len: 0,
+        }
+    }
+
+    /// 写入数据
+    pub fn update(&mut self, input: &[u8]) {
+        self.len = self.len.wrapping_add(input.len() as u64);
+        let state = &mut self.state;
+        self.buffer.update(input, |block| compress_sha1(state, block));
+    }
+
+    /// 计算最终的哈希值
+    pub fn finalize(mut self) -> [u8; 20] {
+        let bit_len = self.len.wrapping_mul(8);
+        let state = &mut self.state;
+        self.buffer.pad(&bit_len.to_be_bytes(), |block| compress_sha1(state, block));
+
+        let mut digest = [0u8; 20];
+        for (i, value) in self.state.iter().enumerate() {
+            digest[i * 4..(i + 1) * 4].copy_from_slice(&value.to_be_bytes());
         }
+        digest
+    }
+}
 
-        hash[0] = hash[0].wrapping_add(a);
-        hash[1] = hash[1].wrapping_add(b);
-        hash[2] = hash[2].wrapping_add(c);
-        hash[3] = hash[3].wrapping_add(d);
-        hash[4] = hash[4].wrapping_add(e);
-        hash[5] = hash[5].wrapping_add(f);
-        hash[6] = hash[6].wrapping_add(g);
-        hash[7] = hash[7].wrapping_add(h);
+crate::digest::impl_digest!(Sha1, 20, 64);
+
+/// HMAC (RFC 2104)
+///
+/// # Example
+/// ```rust
+/// use light_tool::hmac::{Hmac, Sha256};
+/// let mut mac = Hmac::<Sha256>::new(b"key");
+/// mac.update(b"The quick brown fox ");
+/// mac.update(b"jumps over the lazy dog");
+/// println!("hmac: {:?}", mac.finalize())
+/// ```
+#[derive(Clone)]
+pub struct Hmac<D: Digest> {
+    inner: D,
+    outer_key: Vec<u8>,
+}
+
+impl<D: Digest> Hmac<D> {
+    /// 使用密钥初始化，密钥长于分组长度时先做一次哈希
+    pub fn new(key: &[u8]) -> Self {
+        let mut block = if key.len() > D::block_size() {
+            D::digest(key).as_ref().to_vec()
+        } else {
+            key.to_vec()
+        };
+        block.resize(D::block_size(), 0);
+
+        let inner_key: Vec<u8> = block.iter().map(|b| b ^ 0x36).collect();
+        let outer_key: Vec<u8> = block.iter().map(|b| b ^ 0x5c).collect();
+
+        let mut inner = D::new();
+        inner.update(&inner_key);
+        Hmac { inner, outer_key }
+    }
+
+    /// 写入消息
+    pub fn update(&mut self, input: &[u8]) {
+        self.inner.update(input);
     }
 
-    // 格式化为十六进制字符串
-    let mut result = String::new();
-    for &value in &hash {
-        write!(&mut result, "{:08x}", value).expect("Failed to write hash");
+    /// 计算最终的消息认证码
+    pub fn finalize(self) -> Vec<u8> {
+        let inner_hash = self.inner.finalize();
+        let mut outer = D::new();
+        outer.update(&self.outer_key);
+        outer.update(inner_hash.as_ref());
+        outer.finalize().as_ref().to_vec()
     }
 
-    result
+    /// 以常量时间校验消息认证码
+    pub fn verify(self, tag: &[u8]) -> bool {
+        ct::eq(self.finalize(), tag)
+    }
+}
+
+/// 计算 HMAC
+///
+/// # Example
+/// ```rust
+/// use light_tool::hmac::{self, Sha256};
+/// println!("hmac: {:?}", hmac::sign::<Sha256, _, _>("key", "Hello, world!"))
+/// ```
+pub fn sign<D, K, T>(key: K, input: T) -> Vec<u8>
+where
+    D: Digest,
+    K: AsRef<[u8]>,
+    T: AsRef<[u8]>,
+{
+    let mut mac = Hmac::<D>::new(key.as_ref());
+    mac.update(input.as_ref());
+    mac.finalize()
+}
+
+/// 计算 HMAC，返回十六进制字符串
+///
+/// # Example
+/// ```rust
+/// use light_tool::hmac;
+/// use light_tool::md5::Md5;
+/// println!("hmac: {}", hmac::sign_hex::<Md5, _, _>("key", "Hello, world!"))
+/// ```
+pub fn sign_hex<D, K, T>(key: K, input: T) -> String
+where
+    D: Digest,
+    K: AsRef<[u8]>,
+    T: AsRef<[u8]>,
+{
+    hex::encode(sign::<D, K, T>(key, input))
+}
+
+/// 以常量时间校验 HMAC
+///
+/// # Example
+/// ```rust
+/// use light_tool::hmac::{self, Sha256};
+/// let tag = hmac::sign::<Sha256, _, _>("key", "Hello, world!");
+/// assert!(hmac::verify::<Sha256, _, _>("key", "Hello, world!", &tag))
+/// ```
+pub fn verify<D, K, T>(key: K, input: T, tag: &[u8]) -> bool
+where
+    D: Digest,
+    K: AsRef<[u8]>,
+    T: AsRef<[u8]>,
+{
+    let mut mac = Hmac::<D>::new(key.as_ref());
+    mac.update(input.as_ref());
+    mac.verify(tag)
+}
+
+/// HMAC-SHA256
+///
+/// # Example
+/// ```rust
+/// use light_tool::hmac;
+/// println!("hmac-sha256: {:?}", hmac::hmac_sha256("key", "Hello, world!"))
+/// ```
+pub fn hmac_sha256<K, T>(key: K, input: T) -> Vec<u8>
+where
+    K: AsRef<[u8]>,
+    T: AsRef<[u8]>,
+{
+    sign::<Sha256, K, T>(key, input)
+}
+
+/// HMAC-MD5
+///
+/// # Example
+/// ```rust
+/// use light_tool::hmac;
+/// println!("hmac-md5: {:?}", hmac::hmac_md5("key", "Hello, world!"))
+/// ```
+pub fn hmac_md5<K, T>(key: K, input: T) -> Vec<u8>
+where
+    K: AsRef<[u8]>,
+    T: AsRef<[u8]>,
+{
+    sign::<Md5, K, T>(key, input)
+}
+
+/// SHA-256 哈希函数
+///
+/// # Example
+/// ```rust
+/// use light_tool::hmac;
+/// println!("sha256: {}", hmac::sha256("Hello, world!"))
+/// ```
+pub fn sha256<T>(input: T) -> String
+where
+    T: AsRef<[u8]>,
+{
+    let mut sha256 = Sha256::new();
+    sha256.update(input.as_ref());
+    hex::encode(sha256.finalize())
+}
+
+/// SHA-1 哈希函数
+///
+/// # Example
+/// ```rust
+/// use light_tool::hmac;
+/// println!("sha1: {}", hmac::sha1("Hello, world!"))
+/// ```
+pub fn sha1<T>(input: T) -> String
+where
+    T: AsRef<[u8]>,
+{
+    Sha1::hex_digest(input)
+}
+
+/// SHA-224 哈希函数
+///
+/// # Example
+/// ```rust
+/// use light_tool::hmac;
+/// println!("sha224: {}", hmac::sha224("Hello, world!"))
+/// ```
+pub fn sha224<T>(input: T) -> String
+where
+    T: AsRef<[u8]>,
+{
+    Sha224::hex_digest(input)
+}
+
+/// SHA-384 哈希函数
+///
+/// # Example
+/// ```rust
+/// use light_tool::hmac;
+/// println!("sha384: {}", hmac::sha384("Hello, world!"))
+/// ```
+pub fn sha384<T>(input: T) -> String
+where
+    T: AsRef<[u8]>,
+{
+    Sha384::hex_digest(input)
+}
+
+/// SHA-512 哈希函数
+///
+/// # Example
+/// ```rust
+/// use light_tool::hmac;
+/// println!("sha512: {}", hmac::sha512("Hello, world!"))
+/// ```
+pub fn sha512<T>(input: T) -> String
+where
+    T: AsRef<[u8]>,
+{
+    Sha512::hex_digest(input)
+}
+
+/// SHA-256 处理 64 字节块
+fn compress256(state: &mut [u32; 8], block: &[u8; 64]) {
+    let mut w = [0u32; 64];
+    for i in 0..16 {
+        w[i] = u32::from_be_bytes([
+            block[i * 4],
+            block[i * 4 + 1],
+            block[i * 4 + 2],
+            block[i * 4 + 3],
+        ]);
+    }
+    for i in 16..64 {
+        let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+        let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+        w[i] = w[i - 16]
+            .wrapping_add(s0)
+            .wrapping_add(w[i - 7])
+            .wrapping_add(s1);
+    }
+
+    let mut a = state[0];
+    let mut b = state[1];
+    let mut c = state[2];
+    let mut d = state[3];
+    let mut e = state[4];
+    let mut f = state[5];
+    let mut g = state[6];
+    let mut h = state[7];
+
+    for i in 0..64 {
+        let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+        let ch = (e & f) ^ (!e & g);
+        let temp1 = h
+            .wrapping_add(s1)
+            .wrapping_add(ch)
+            .wrapping_add(K[i])
+            .wrapping_add(w[i]);
+        let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+        let maj = (a & b) ^ (a & c) ^ (b & c);
+        let temp2 = s0.wrapping_add(maj);
+
+        h = g;
+        g = f;
+        f = e;
+        e = d.wrapping_add(temp1);
+        d = c;
+        c = b;
+        b = a;
+        a = temp1.wrapping_add(temp2);
+    }
+
+    state[0] = state[0].wrapping_add(a);
+    state[1] = state[1].wrapping_add(b);
+    state[2] = state[2].wrapping_add(c);
+    state[3] = state[3].wrapping_add(d);
+    state[4] = state[4].wrapping_add(e);
+    state[5] = state[5].wrapping_add(f);
+    state[6] = state[6].wrapping_add(g);
+    state[7] = state[7].wrapping_add(h);
+}
+
+/// SHA-512 处理 128 字节块
+fn compress512(state: &mut [u64; 8], block: &[u8; 128]) {
+    let mut w = [0u64; 80];
+    for i in 0..16 {
+        w[i] = u64::from_be_bytes(block[i * 8..(i + 1) * 8].try_into().unwrap());
+    }
+    for i in 16..80 {
+        let s0 = w[i - 15].rotate_right(1) ^ w[i - 15].rotate_right(8) ^ (w[i - 15] >> 7);
+        let s1 = w[i - 2].rotate_right(19) ^ w[i - 2].rotate_right(61) ^ (w[i - 2] >> 6);
+        w[i] = w[i - 16]
+            .wrapping_add(s0)
+            .wrapping_add(w[i - 7])
+            .wrapping_add(s1);
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = *state;
+
+    for i in 0..80 {
+        let s1 = e.rotate_right(14) ^ e.rotate_right(18) ^ e.rotate_right(41);
+        let ch = (e & f) ^ (!e & g);
+        let temp1 = h
+            .wrapping_add(s1)
+            .wrapping_add(ch)
+            .wrapping_add(K512[i])
+            .wrapping_add(w[i]);
+        let s0 = a.rotate_right(28) ^ a.rotate_right(34) ^ a.rotate_right(39);
+        let maj = (a & b) ^ (a & c) ^ (b & c);
+        let temp2 = s0.wrapping_add(maj);
+
+        h = g;
+        g = f;
+        f = e;
+        e = d.wrapping_add(temp1);
+        d = c;
+        c = b;
+        b = a;
+        a = temp1.wrapping_add(temp2);
+    }
+
+    for (s, v) in state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+        *s = s.wrapping_add(v);
+    }
+}
+
+/// SHA-1 处理 64 字节块
+fn compress_sha1(state: &mut [u32; 5], block: &[u8; 64]) {
+    let mut w = [0u32; 80];
+    for i in 0..16 {
+        w[i] = u32::from_be_bytes(block[i * 4..(i + 1) * 4].try_into().unwrap());
+    }
+    for i in 16..80 {
+        w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e] = *state;
+
+    for (i, &wi) in w.iter().enumerate() {
+        let (f, k) = match i {
+            0..=19 => ((b & c) | (!b & d), 0x5a827999),
+            20..=39 => (b ^ c ^ d, 0x6ed9eba1),
+            40..=59 => ((b & c) | (b & d) | (c & d), 0x8f1bbcdc),
+            _ => (b ^ c ^ d, 0xca62c1d6),
+        };
+        let temp = a
+            .rotate_left(5)
+            .wrapping_add(f)
+            .wrapping_add(e)
+            .wrapping_add(k)
+            .wrapping_add(wi);
+        e = d;
+        d = c;
+        c = b.rotate_left(30);
+        b = a;
+        a = temp;
+    }
+
+    for (s, v) in state.iter_mut().zip([a, b, c, d, e]) {
+        *s = s.wrapping_add(v);
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::hex::encode as hex;
 
     #[test]
     fn test_sha256() {
         let input = "Hello, world!";
         let expected_output = "315f5bdb76d078c43b8ac0064e4a0164612b1fce77c869345bfc94c75894edd3";
         let actual_output = sha256(input);
-        assert_eq!(expected_output, actual_output)
+        assert_eq!(expected_output, actual_output);
+
+        assert_eq!(sha256(b""), "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+        assert_eq!(
+            sha256("abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"),
+            "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"
+        );
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_sha256_streaming() {
+        // SHA-1/SHA-256 分组 64 字节，SHA-384/SHA-512 分组 128 字节
+        crate::digest::assert_streaming::<Sha1>();
+        crate::digest::assert_streaming::<Sha224>();
+        crate::digest::assert_streaming::<Sha256>();
+        crate::digest::assert_streaming::<Sha384>();
+        crate::digest::assert_streaming::<Sha512>();
+        assert_eq!(hex(Sha256::digest(crate::digest::test_data(1000))), "4e4c294b331f7a2099a379bec34b9f9fc03dc46ab465d998f4d683da53487e6d");
+    }
+
+    #[test]
+    fn test_hmac_sha256() {
+        // RFC 4231
+        assert_eq!(
+            hex(hmac_sha256([0x0b; 20], "Hi There")),
+            "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7"
+        );
+        assert_eq!(
+            hex(hmac_sha256("Jefe", "what do ya want for nothing?")),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+        assert_eq!(
+            hex(hmac_sha256([0xaa; 131], "Test Using Larger Than Block-Size Key - Hash Key First")),
+            "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"
+        );
+    }
+
+    #[test]
+    fn test_hmac_md5() {
+        // RFC 2202
+        assert_eq!(
+            hex(hmac_md5([0x0b; 16], "Hi There")),
+            "9294727a3638bb1c13f48ef8158bfc9d"
+        );
+        assert_eq!(
+            sign_hex::<Md5, _, _>("Jefe", "what do ya want for nothing?"),
+            "750c783e6ab0b503eaa86e310a5db738"
+        );
+    }
+
+    #[test]
+    fn test_hmac_verify() {
+        let tag = sign::<Sha256, _, _>("secret", "payload");
+        assert!(verify::<Sha256, _, _>("secret", "payload", &tag));
+        assert!(!verify::<Sha256, _, _>("secret", "payload!", &tag));
+        assert!(!verify::<Sha256, _, _>("secret", "payload", &tag[..31]));
+
+        let mut mac = Hmac::<Sha256>::new(b"secret");
+        mac.update(b"pay");
+        mac.update(b"load");
+        assert!(mac.verify(&tag));
+    }
+
+    #[test]
+    fn test_sha1() {
+        assert_eq!(sha1(""), "da39a3ee5e6b4b0d3255bfef95601890afd80709");
+        assert_eq!(sha1("abc"), "a9993e364706816aba3e25717850c26c9cd0d89d");
+        assert_eq!(
+            sha1("abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"),
+            "84983e441c3bd26ebaae4aa1f95129e5e54670f1"
+        );
+    }
+
+    #[test]
+    fn test_sha224() {
+        assert_eq!(sha224(""), "d14a028c2a3a2bc9476102bb288234c415a2b01f828ea62ac5b3e42f");
+        assert_eq!(sha224("abc"), "23097d223405d8228642a477bda255b32aadbce4bda0b3f7e36c9da7");
+        assert_eq!(
+            sha224("abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"),
+            "75388b16512776cc5dba5da1fd890150b0c6455cb4f58b1952522525"
+        );
+    }
+
+    #[test]
+    fn test_sha384() {
+        assert_eq!(
+            sha384("abc"),
+            "cb00753f45a35e8bb5a03d699ac65007272c32ab0eded1631a8b605a43ff5bed8086072ba1e7cc2358baeca134c825a7"
+        );
+        assert_eq!(
+            sha384("abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmnhijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu"),
+            "09330c33f71147e83d192fc782cd1b4753111b173b3b05d22fa08086e3b0f712fcc7c71a557e2db966c3e9fa91746039"
+        );
+    }
+
+    #[test]
+    fn test_sha512() {
+        assert_eq!(
+            sha512(""),
+            "cf83e1357eefb8bdf1542850d66d8007d620e4050b5715dc83f4a921d36ce9ce47d0d13c5d85f2b0ff8318d2877eec2f63b931bd47417a81a538327af927da3e"
+        );
+        assert_eq!(
+            sha512("abc"),
+            "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f"
+        );
+        assert_eq!(
+            sha512("abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmnhijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu"),
+            "8e959b75dae313da8cf4f72814fc143f8f7779c6eb9f7fa17299aeadb6889018501d289e4900f7e4331b99dec4b5433ac7d329eeb6dd26545e96e55b874be909"
+        );
+    }
+
+    #[test]
+    fn test_sha512_streaming() {
+        let data = vec![b'a'; 1_000_000];
+        let mut sha512 = Sha512::new();
+        for chunk in data.chunks(333) {
+            sha512.update(chunk);
+        }
+        assert_eq!(
+            hex(sha512.finalize()),
+            "e718483d0ce769644e2e42c7bc15b4638e1f98b13b2044285632a803afa973ebde0ff244877ea60a4cb0432ce577c31beb009c5c2c49aa2e4eadb217ad8cc09b"
+        );
+    }
+
+    #[test]
+    fn test_hmac_sha512() {
+        // RFC 4231
+        assert_eq!(
+            sign_hex::<Sha512, _, _>([0x0b; 20], "Hi There"),
+            "87aa7cdea5ef619d4ff0b4241a1d6cb02379f4e2ce4ec2787ad0b30545e17cdedaa833b7d6b8a702038b274eaea3f4e4be9d914eeb61f1702e696c203a126854"
+        );
+        assert_eq!(
+            sign_hex::<Sha384, _, _>("Jefe", "what do ya want for nothing?"),
+            "af45d2e376484031617f78d2b58a6b1b9c7ef464f5a01b47e42ec3736322445e8e2240ca5e69e2c78b3239ecfab21649"
+        );
+    }
+}
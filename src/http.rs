@@ -5,6 +5,7 @@ use std::net::{TcpStream, ToSocketAddrs};
 use std::time::Duration;
 use std::fmt::Write as FmtWrite;
 use std::fs;
+use crate::percent::{self, EncodeSet};
 
 struct HttpClient {
     host: String,
@@ -37,7 +38,7 @@ impl HttpClient {
     ) -> Result<HttpResponse, Box<dyn  Error>> {
         let addr = format!("{}:{}", self.host, self.port);
         let mut addrs = addr.to_socket_addrs()?;
-        let socket_addr = addrs.next().ok_or_else(|| std::io::Error::new(std::io::ErrorKind::Other, "Could not resolve address"))?;
+        let socket_addr = addrs.next().ok_or_else(|| std::io::Error::other("Could not resolve address"))?;
 
         let mut stream = TcpStream::connect_timeout(&socket_addr, Duration::from_secs(2))?;
 
@@ -79,11 +80,14 @@ fn client(url: &str, timeout: Duration) -> Result<HttpClient, Box<dyn Error>> {
     let url = url.strip_prefix("http://")
         .or_else(|| url.strip_prefix("https://"))
         .ok_or("Invalid URL: Missing protocol (http or https)")?;
+    // 片段不发送给服务器
+    let url = url.split('#').next().unwrap_or(url);
 
-    // ["127.0.0.1:9090", "ping"]
-    let mut parts = url.splitn(2, '/');
-    let host_and_port = parts.next().ok_or("Invalid URL: Missing host")?;
-    let path = format!("/{}", parts.next().unwrap_or(""));
+    // ("127.0.0.1:9090", "/ping")，主机部分在 `/` 或 `?` 处结束
+    let (host_and_port, path) = url.split_at(url.find(['/', '?']).unwrap_or(url.len()));
+    let path = path.strip_prefix('/').unwrap_or(path);
+    // 编码路径与查询中的空格、中文等字符，已编码的 `%XX` 保持不变
+    let path = escape_percent(&percent::encode(format!("/{}", path), &EncodeSet::URI));
 
     // ["127.0.0.1", "9090"]
     let mut host_parts = host_and_port.splitn(2, ':');
@@ -97,6 +101,21 @@ fn client(url: &str, timeout: Duration) -> Result<HttpClient, Box<dyn Error>> {
     Ok(HttpClient::new(&host, port, &path, timeout))
 }
 
+/// 将后面不是两位十六进制数字的 `%` 编码为 `%25`
+fn escape_percent(path: &str) -> String {
+    let bytes = path.as_bytes();
+    let mut output = String::with_capacity(path.len());
+    for (i, c) in path.char_indices() {
+        let escaped = bytes.get(i + 1..i + 3).is_some_and(|digits| digits.iter().all(u8::is_ascii_hexdigit));
+        if c == '%' && !escaped {
+            output.push_str("%25");
+        } else {
+            output.push(c);
+        }
+    }
+    output
+}
+
 fn parse_http_response(response: &[u8]) -> Result<(String, Vec<u8>), Box<dyn Error>> {
     // 在字节数组上查找 "\r\n\r\n" 分隔符
     if let Some(pos) = response.windows(4).position(|window| window == b"\r\n\r\n") {
@@ -112,6 +131,51 @@ fn parse_http_response(response: &[u8]) -> Result<(String, Vec<u8>), Box<dyn Err
     }
 }
 
+/// 构造查询字符串，键与值均进行百分号编码
+///
+/// # Example
+///
+/// ```rust
+/// use light_tool::http;
+/// assert_eq!(http::build_query(&[("q", "rust 教程"), ("a&b", "1=2")]), "q=rust%20%E6%95%99%E7%A8%8B&a%26b=1%3D2")
+/// ```
+pub fn build_query<K: AsRef<str>, V: AsRef<str>>(params: &[(K, V)]) -> String {
+    params
+        .iter()
+        .map(|(key, value)| {
+            format!("{}={}", percent::encode(key.as_ref(), &EncodeSet::QUERY), percent::encode(value.as_ref(), &EncodeSet::QUERY))
+        })
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+/// 在 URL 后追加查询参数，已有查询时以 `&` 连接
+///
+/// # Example
+///
+/// ```rust
+/// use light_tool::http;
+/// let url = http::with_query("http://example.com/search?page=1", &[("q", "a b")]);
+/// assert_eq!(url, "http://example.com/search?page=1&q=a%20b")
+/// ```
+pub fn with_query<K: AsRef<str>, V: AsRef<str>>(url: &str, params: &[(K, V)]) -> String {
+    if params.is_empty() {
+        return url.to_string();
+    }
+    let (url, fragment) = match url.split_once('#') {
+        Some((url, fragment)) => (url, format!("#{}", fragment)),
+        None => (url, String::new()),
+    };
+    let separator = if !url.contains('?') {
+        "?"
+    } else if url.ends_with('?') || url.ends_with('&') {
+        ""
+    } else {
+        "&"
+    };
+    format!("{}{}{}{}", url, separator, build_query(params), fragment)
+}
+
 /// GET Request
 ///
 /// # Example
@@ -232,4 +296,22 @@ mod tests {
             println!("Download failed: {:?}", e);
         }
     }
+
+    #[test]
+    fn test_query() {
+        assert_eq!(build_query::<&str, &str>(&[]), "");
+        assert_eq!(with_query("http://example.com", &[("q", "a+b"), ("lang", "中文")]), "http://example.com?q=a%2Bb&lang=%E4%B8%AD%E6%96%87");
+        assert_eq!(with_query("http://example.com/?", &[("q", "1")]), "http://example.com/?q=1");
+        assert_eq!(with_query("http://example.com/a#top", &[("q", "1")]), "http://example.com/a?q=1#top");
+
+        let client = client("http://127.0.0.1:9090/搜索 页?q=a b&x=%20#frag", DEFAULT_TIMEOUT).unwrap();
+        assert_eq!(client.path, "/%E6%90%9C%E7%B4%A2%20%E9%A1%B5?q=a%20b&x=%20");
+        let path = super::client("http://127.0.0.1:9090/p?discount=100%&x=%2g&y=%4F%", DEFAULT_TIMEOUT).unwrap().path;
+        assert_eq!(path, "/p?discount=100%25&x=%252g&y=%4F%25");
+
+        // 没有路径时 with_query 生成的 URL 也能直接请求
+        let url = with_query("http://example.com:8080", &[("q", "a+b")]);
+        let client = super::client(&url, DEFAULT_TIMEOUT).unwrap();
+        assert_eq!((client.host.as_str(), client.port, client.path.as_str()), ("example.com", 8080, "/?q=a%2Bb"));
+    }
 }
@@ -1,6 +1,6 @@
 use std::process;
 use std::sync::atomic::{AtomicU32, Ordering};
-use crate::{random, timestamp, mac, md5};
+use crate::{random, timestamp, mac, md5, hex};
 use crate::lazy::Lazy;
 use std::sync::Mutex;
 use crate::mac::MAC;
@@ -134,7 +134,7 @@ impl ObjectId {
 
     /// Convert the ObjectId to a hexadecimal string
     pub fn to_hex(&self) -> String {
-        self.bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+        hex::encode(self.bytes)
     }
 }
 
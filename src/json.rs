@@ -0,0 +1,428 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// JSON 对象
+pub type Map = BTreeMap<String, Value>;
+
+/// JSON 值，`to_string()` 输出紧凑格式
+///
+/// # Example
+/// ```rust
+/// use light_tool::json::{Map, Value};
+/// let mut map = Map::new();
+/// map.insert("id".to_string(), Value::from(42));
+/// map.insert("tags".to_string(), Value::from(vec![Value::from("a"), Value::Null]));
+/// let value = Value::from(map);
+/// assert_eq!(value.get("id").and_then(Value::as_u64), Some(42));
+/// assert_eq!(value.to_string(), r#"{"id":42,"tags":["a",null]}"#)
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Value>),
+    Object(Map),
+}
+
+impl Value {
+    /// 获取对象中的字段
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(map) => map.get(key),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// 非负整数
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            Value::Number(n) if *n >= 0.0 && n.fract() == 0.0 && *n <= u64::MAX as f64 => Some(*n as u64),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&Vec<Value>> {
+        match self {
+            Value::Array(a) => Some(a),
+            _ => None,
+        }
+    }
+
+    pub fn as_object(&self) -> Option<&Map> {
+        match self {
+            Value::Object(map) => Some(map),
+            _ => None,
+        }
+    }
+}
+
+impl From<bool> for Value {
+    fn from(b: bool) -> Self {
+        Value::Bool(b)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Self {
+        Value::String(s.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(s: String) -> Self {
+        Value::String(s)
+    }
+}
+
+macro_rules! impl_from_number {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Value {
+            fn from(n: $t) -> Self {
+                Value::Number(n as f64)
+            }
+        })*
+    };
+}
+
+impl_from_number!(i32, i64, u32, u64, usize, f32, f64);
+
+impl From<Vec<Value>> for Value {
+    fn from(a: Vec<Value>) -> Self {
+        Value::Array(a)
+    }
+}
+
+impl From<Map> for Value {
+    fn from(map: Map) -> Self {
+        Value::Object(map)
+    }
+}
+
+/// 紧凑格式序列化
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Null => f.write_str("null"),
+            Value::Bool(b) => write!(f, "{}", b),
+            Value::Number(n) if !n.is_finite() => f.write_str("null"),
+            // 整数不输出小数点
+            Value::Number(n) if n.fract() == 0.0 && n.abs() < 1e15 => write!(f, "{}", *n as i64),
+            Value::Number(n) => write!(f, "{}", n),
+            Value::String(s) => write_string(f, s),
+            Value::Array(a) => {
+                f.write_str("[")?;
+                for (i, v) in a.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(",")?;
+                    }
+                    write!(f, "{}", v)?;
+                }
+                f.write_str("]")
+            }
+            Value::Object(map) => {
+                f.write_str("{")?;
+                for (i, (k, v)) in map.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(",")?;
+                    }
+                    write_string(f, k)?;
+                    write!(f, ":{}", v)?;
+                }
+                f.write_str("}")
+            }
+        }
+    }
+}
+
+fn write_string(f: &mut fmt::Formatter<'_>, s: &str) -> fmt::Result {
+    f.write_str("\"")?;
+    for c in s.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    f.write_str("\"")
+}
+
+/// JSON 解析
+///
+/// # Example
+/// ```rust
+/// use light_tool::json;
+/// let value = json::parse(r#"{"name": "light-tool", "tags": ["rust", 1, true, null]}"#).unwrap();
+/// assert_eq!(value.get("name").unwrap().as_str(), Some("light-tool"));
+/// assert_eq!(value.to_string(), r#"{"name":"light-tool","tags":["rust",1,true,null]}"#)
+/// ```
+pub fn parse(input: &str) -> Result<Value, String> {
+    let mut parser = Parser { input: input.as_bytes(), pos: 0 };
+    let value = parser.value(0)?;
+    parser.whitespace();
+    if parser.pos != parser.input.len() {
+        return Err(parser.error("trailing characters"));
+    }
+    Ok(value)
+}
+
+/// 最大嵌套深度，防止恶意输入导致栈溢出
+const MAX_DEPTH: usize = 128;
+
+struct Parser<'a> {
+    input: &'a [u8],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn error(&self, msg: &str) -> String {
+        format!("Invalid JSON at offset {}: {}", self.pos, msg)
+    }
+
+    fn whitespace(&mut self) {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.input.get(self.pos) {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, literal: &str) -> Result<(), String> {
+        if self.input[self.pos..].starts_with(literal.as_bytes()) {
+            self.pos += literal.len();
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected `{}`", literal)))
+        }
+    }
+
+    fn value(&mut self, depth: usize) -> Result<Value, String> {
+        if depth > MAX_DEPTH {
+            return Err(self.error("nesting too deep"));
+        }
+        self.whitespace();
+        match self.input.get(self.pos) {
+            Some(b'n') => self.expect("null").map(|_| Value::Null),
+            Some(b't') => self.expect("true").map(|_| Value::Bool(true)),
+            Some(b'f') => self.expect("false").map(|_| Value::Bool(false)),
+            Some(b'"') => self.string().map(Value::String),
+            Some(b'[') => self.array(depth),
+            Some(b'{') => self.object(depth),
+            Some(b'-' | b'0'..=b'9') => self.number(),
+            Some(_) => Err(self.error("unexpected character")),
+            None => Err(self.error("unexpected end of input")),
+        }
+    }
+
+    fn array(&mut self, depth: usize) -> Result<Value, String> {
+        self.pos += 1;
+        let mut array = Vec::new();
+        self.whitespace();
+        if self.input.get(self.pos) == Some(&b']') {
+            self.pos += 1;
+            return Ok(Value::Array(array));
+        }
+        loop {
+            array.push(self.value(depth + 1)?);
+            self.whitespace();
+            match self.input.get(self.pos) {
+                Some(b',') => self.pos += 1,
+                Some(b']') => {
+                    self.pos += 1;
+                    return Ok(Value::Array(array));
+                }
+                _ => return Err(self.error("expected `,` or `]`")),
+            }
+        }
+    }
+
+    fn object(&mut self, depth: usize) -> Result<Value, String> {
+        self.pos += 1;
+        let mut map = Map::new();
+        self.whitespace();
+        if self.input.get(self.pos) == Some(&b'}') {
+            self.pos += 1;
+            return Ok(Value::Object(map));
+        }
+        loop {
+            self.whitespace();
+            if self.input.get(self.pos) != Some(&b'"') {
+                return Err(self.error("expected object key"));
+            }
+            let key = self.string()?;
+            self.whitespace();
+            self.expect(":")?;
+            map.insert(key, self.value(depth + 1)?);
+            self.whitespace();
+            match self.input.get(self.pos) {
+                Some(b',') => self.pos += 1,
+                Some(b'}') => {
+                    self.pos += 1;
+                    return Ok(Value::Object(map));
+                }
+                _ => return Err(self.error("expected `,` or `}`")),
+            }
+        }
+    }
+
+    fn number(&mut self) -> Result<Value, String> {
+        let start = self.pos;
+        if self.input[self.pos] == b'-' {
+            self.pos += 1;
+        }
+        match self.input.get(self.pos) {
+            Some(b'0') => self.pos += 1,
+            Some(b'1'..=b'9') => self.digits(),
+            _ => return Err(self.error("invalid number")),
+        }
+        if self.input.get(self.pos) == Some(&b'.') {
+            self.pos += 1;
+            if !matches!(self.input.get(self.pos), Some(b'0'..=b'9')) {
+                return Err(self.error("invalid number"));
+            }
+            self.digits();
+        }
+        if let Some(b'e' | b'E') = self.input.get(self.pos) {
+            self.pos += 1;
+            if let Some(b'+' | b'-') = self.input.get(self.pos) {
+                self.pos += 1;
+            }
+            if !matches!(self.input.get(self.pos), Some(b'0'..=b'9')) {
+                return Err(self.error("invalid number"));
+            }
+            self.digits();
+        }
+        // 以上只匹配 ASCII 字符，切片必为合法 UTF-8
+        let text = std::str::from_utf8(&self.input[start..self.pos]).unwrap();
+        text.parse().map(Value::Number).map_err(|_| self.error("invalid number"))
+    }
+
+    fn digits(&mut self) {
+        while let Some(b'0'..=b'9') = self.input.get(self.pos) {
+            self.pos += 1;
+        }
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        self.pos += 1;
+        let mut bytes = Vec::new();
+        loop {
+            match self.input.get(self.pos) {
+                None => return Err(self.error("unterminated string")),
+                Some(b'"') => {
+                    self.pos += 1;
+                    // 输入为 &str，未转义部分必为合法 UTF-8
+                    return Ok(String::from_utf8(bytes).unwrap());
+                }
+                Some(b'\\') => {
+                    self.pos += 1;
+                    let c = match self.input.get(self.pos) {
+                        Some(b'"') => '"',
+                        Some(b'\\') => '\\',
+                        Some(b'/') => '/',
+                        Some(b'b') => '\u{8}',
+                        Some(b'f') => '\u{c}',
+                        Some(b'n') => '\n',
+                        Some(b'r') => '\r',
+                        Some(b't') => '\t',
+                        Some(b'u') => self.unicode_escape()?,
+                        _ => return Err(self.error("invalid escape")),
+                    };
+                    self.pos += 1;
+                    bytes.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+                }
+                Some(&b) if b < 0x20 => return Err(self.error("control character in string")),
+                Some(&b) => {
+                    bytes.push(b);
+                    self.pos += 1;
+                }
+            }
+        }
+    }
+
+    /// 解析 `\uXXXX`，支持代理对；结束时 pos 指向最后一个十六进制字符
+    fn unicode_escape(&mut self) -> Result<char, String> {
+        let high = self.hex4()?;
+        if !(0xd800..0xdc00).contains(&high) {
+            return char::from_u32(high).ok_or_else(|| self.error("invalid unicode escape"));
+        }
+        self.pos += 1;
+        self.expect("\\u")?;
+        self.pos -= 1;
+        let low = self.hex4()?;
+        if !(0xdc00..0xe000).contains(&low) {
+            return Err(self.error("invalid surrogate pair"));
+        }
+        char::from_u32(0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00))
+            .ok_or_else(|| self.error("invalid unicode escape"))
+    }
+
+    fn hex4(&mut self) -> Result<u32, String> {
+        let digits = self.input.get(self.pos + 1..self.pos + 5).ok_or_else(|| self.error("invalid unicode escape"))?;
+        let mut code = 0;
+        for &d in digits {
+            code = code * 16 + (d as char).to_digit(16).ok_or_else(|| self.error("invalid unicode escape"))?;
+        }
+        self.pos += 4;
+        Ok(code)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let value = parse(r#" {"a": [1, -2.5, 3e2, true, false, null], "b": {"c": "x\"\\\/\n\u00e9\ud83d\ude00"}} "#).unwrap();
+        let a = value.get("a").unwrap().as_array().unwrap();
+        assert_eq!(a[0].as_u64(), Some(1));
+        assert_eq!(a[1].as_f64(), Some(-2.5));
+        assert_eq!(a[2].as_u64(), Some(300));
+        assert_eq!(a[3].as_bool(), Some(true));
+        assert_eq!(a[5], Value::Null);
+        assert_eq!(value.get("b").unwrap().get("c").unwrap().as_str(), Some("x\"\\/\né😀"));
+
+        for invalid in ["", "{", "[1,]", "{\"a\" 1}", "01", "1.", "\"\\x\"", "\"\\ud83d\"", "nul", "1 2"] {
+            assert!(parse(invalid).is_err(), "{}", invalid);
+        }
+        assert!(parse(&"[".repeat(1000)).is_err());
+    }
+
+    #[test]
+    fn test_serialize() {
+        let mut map = Map::new();
+        map.insert("s".to_string(), "a\"b\u{1}".into());
+        map.insert("n".to_string(), 1516239022u64.into());
+        map.insert("f".to_string(), 0.5.into());
+        map.insert("a".to_string(), vec![Value::Null, true.into()].into());
+        let value = Value::Object(map);
+        let text = value.to_string();
+        assert_eq!(text, r#"{"a":[null,true],"f":0.5,"n":1516239022,"s":"a\"b\u0001"}"#);
+        assert_eq!(parse(&text).unwrap(), value);
+    }
+}
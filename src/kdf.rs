@@ -0,0 +1,207 @@
+use crate::digest::Digest;
+use crate::hmac::{Hmac, Sha256, Sha512};
+
+/// PBKDF2 (RFC 8018)，以 HMAC 为伪随机函数
+///
+/// # 参数:
+/// - `password`: 口令
+/// - `salt`: 盐值，建议至少 16 字节随机数
+/// - `iterations`: 迭代次数，至少为 1，HMAC-SHA256 建议不低于 600000
+/// - `len`: 输出长度（字节）
+///
+/// # Example
+/// ```rust
+/// use light_tool::kdf;
+/// use light_tool::hmac::Sha256;
+/// println!("pbkdf2: {:?}", kdf::pbkdf2::<Sha256>(b"password", b"salt", 1000, 32).unwrap());
+/// assert!(kdf::pbkdf2::<Sha256>(b"password", b"salt", 0, 32).is_err())
+/// ```
+pub fn pbkdf2<D: Digest>(password: &[u8], salt: &[u8], iterations: u32, len: usize) -> Result<Vec<u8>, String> {
+    if iterations == 0 {
+        return Err("PBKDF2 iterations must be at least 1".to_string());
+    }
+    if len as u64 > u32::MAX as u64 * D::output_size() as u64 {
+        return Err(format!("PBKDF2 output length too long: {}", len));
+    }
+
+    // 密钥只需处理一次，之后每次迭代复制已初始化的 HMAC 状态
+    let prf = Hmac::<D>::new(password);
+    let mut output = Vec::with_capacity(len);
+    let mut block_index = 1u32;
+
+    while output.len() < len {
+        let mut mac = prf.clone();
+        mac.update(salt);
+        mac.update(&block_index.to_be_bytes());
+        let mut u = mac.finalize();
+        let mut block = u.clone();
+
+        for _ in 1..iterations {
+            let mut mac = prf.clone();
+            mac.update(&u);
+            u = mac.finalize();
+            for (b, x) in block.iter_mut().zip(&u) {
+                *b ^= x;
+            }
+        }
+
+        let take = (len - output.len()).min(block.len());
+        output.extend_from_slice(&block[..take]);
+        block_index += 1;
+    }
+
+    Ok(output)
+}
+
+/// PBKDF2-HMAC-SHA256
+///
+/// # Example
+/// ```rust
+/// use light_tool::kdf;
+/// println!("pbkdf2: {:?}", kdf::pbkdf2_sha256("password", "salt", 1000, 32).unwrap())
+/// ```
+pub fn pbkdf2_sha256<P, S>(password: P, salt: S, iterations: u32, len: usize) -> Result<Vec<u8>, String>
+where
+    P: AsRef<[u8]>,
+    S: AsRef<[u8]>,
+{
+    pbkdf2::<Sha256>(password.as_ref(), salt.as_ref(), iterations, len)
+}
+
+/// PBKDF2-HMAC-SHA512
+///
+/// # Example
+/// ```rust
+/// use light_tool::kdf;
+/// println!("pbkdf2: {:?}", kdf::pbkdf2_sha512("password", "salt", 1000, 64).unwrap())
+/// ```
+pub fn pbkdf2_sha512<P, S>(password: P, salt: S, iterations: u32, len: usize) -> Result<Vec<u8>, String>
+where
+    P: AsRef<[u8]>,
+    S: AsRef<[u8]>,
+{
+    pbkdf2::<Sha512>(password.as_ref(), salt.as_ref(), iterations, len)
+}
+
+/// HKDF 提取 (RFC 5869)，由输入密钥材料得到伪随机密钥 PRK
+///
+/// `salt` 为空时按 RFC 使用哈希长度的全 0 字节
+///
+/// # Example
+/// ```rust
+/// use light_tool::kdf;
+/// use light_tool::hmac::Sha256;
+/// println!("prk: {:?}", kdf::hkdf_extract::<Sha256>(b"salt", b"input key material"))
+/// ```
+pub fn hkdf_extract<D: Digest>(salt: &[u8], ikm: &[u8]) -> Vec<u8> {
+    let zeros = vec![0u8; D::output_size()];
+    let salt = if salt.is_empty() { &zeros } else { salt };
+    let mut mac = Hmac::<D>::new(salt);
+    mac.update(ikm);
+    mac.finalize()
+}
+
+/// HKDF 扩展 (RFC 5869)，由 PRK 得到 `len` 字节的输出密钥材料
+///
+/// `len` 不能超过 255 倍哈希长度
+///
+/// # Example
+/// ```rust
+/// use light_tool::kdf;
+/// use light_tool::hmac::Sha256;
+/// let prk = kdf::hkdf_extract::<Sha256>(b"salt", b"input key material");
+/// println!("okm: {:?}", kdf::hkdf_expand::<Sha256>(&prk, b"context", 42).unwrap())
+/// ```
+pub fn hkdf_expand<D: Digest>(prk: &[u8], info: &[u8], len: usize) -> Result<Vec<u8>, String> {
+    if len > 255 * D::output_size() {
+        return Err(format!("HKDF output length too long: {}", len));
+    }
+
+    let prf = Hmac::<D>::new(prk);
+    let mut output = Vec::with_capacity(len);
+    let mut t: Vec<u8> = Vec::new();
+    let mut counter = 1u8;
+
+    while output.len() < len {
+        let mut mac = prf.clone();
+        mac.update(&t);
+        mac.update(info);
+        mac.update(&[counter]);
+        t = mac.finalize();
+
+        let take = (len - output.len()).min(t.len());
+        output.extend_from_slice(&t[..take]);
+        counter = counter.wrapping_add(1);
+    }
+
+    Ok(output)
+}
+
+/// HKDF 提取并扩展
+///
+/// # Example
+/// ```rust
+/// use light_tool::kdf;
+/// use light_tool::hmac::Sha256;
+/// println!("okm: {:?}", kdf::hkdf::<Sha256>(b"salt", b"input key material", b"context", 32).unwrap())
+/// ```
+pub fn hkdf<D: Digest>(salt: &[u8], ikm: &[u8], info: &[u8], len: usize) -> Result<Vec<u8>, String> {
+    hkdf_expand::<D>(&hkdf_extract::<D>(salt, ikm), info, len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hex::encode as hex;
+    use crate::hmac::Sha1;
+
+    #[test]
+    fn test_pbkdf2() {
+        // RFC 6070
+        assert_eq!(hex(pbkdf2::<Sha1>(b"password", b"salt", 1, 20).unwrap()), "0c60c80f961f0e71f3a9b524af6012062fe037a6");
+        assert_eq!(hex(pbkdf2::<Sha1>(b"password", b"salt", 4096, 20).unwrap()), "4b007901b765489abead49d926f721d065a429c1");
+        assert_eq!(
+            hex(pbkdf2::<Sha1>(b"passwordPASSWORDpassword", b"saltSALTsaltSALTsaltSALTsaltSALTsalt", 4096, 25).unwrap()),
+            "3d2eec4fe41c849b80c8d83662c0e44a8b291a964cf2f07038"
+        );
+
+        // RFC 7914
+        assert_eq!(
+            hex(pbkdf2_sha256("passwd", "salt", 1, 64).unwrap()),
+            "55ac046e56e3089fec1691c22544b605f94185216dde0465e68b9d57c20dacbc49ca9cccf179b645991664b39d77ef317c71b845b1e30bd509112041d3a19783"
+        );
+        assert_eq!(
+            hex(pbkdf2_sha256("Password", "NaCl", 80000, 64).unwrap()),
+            "4ddcd8f60b98be21830cee5ef22701f9641a4418d04c0414aeff08876b34ab56a1d425a1225833549adb841b51c9b3176a272bdebba1d078478f62b397f33c8d"
+        );
+
+        assert_eq!(
+            hex(pbkdf2_sha512("password", "salt", 1000, 64).unwrap()),
+            "afe6c5530785b6cc6b1c6453384731bd5ee432ee549fd42fb6695779ad8a1c5bf59de69c48f774efc4007d5298f9033c0241d5ab69305e7b64eceeb8d834cfec"
+        );
+
+        assert!(pbkdf2_sha256("password", "salt", 0, 32).is_err());
+    }
+
+    #[test]
+    fn test_hkdf() {
+        // RFC 5869 Test Case 1
+        let ikm = [0x0b; 22];
+        let salt: Vec<u8> = (0x00..=0x0c).collect();
+        let info: Vec<u8> = (0xf0..=0xf9).collect();
+        let prk = hkdf_extract::<Sha256>(&salt, &ikm);
+        assert_eq!(hex(&prk), "077709362c2e32df0ddc3f0dc47bba6390b6c73bb50f9c3122ec844ad7c2b3e5");
+        assert_eq!(
+            hex(hkdf_expand::<Sha256>(&prk, &info, 42).unwrap()),
+            "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf34007208d5b887185865"
+        );
+
+        // RFC 5869 Test Case 3：salt 与 info 为空
+        assert_eq!(
+            hex(hkdf::<Sha256>(&[], &ikm, &[], 42).unwrap()),
+            "8da4e775a563c18f715f802a063c5a31b8a11f5c5ee1879ec3454e5f3c738d2d9d201395faa4b61a96c8"
+        );
+
+        assert!(hkdf_expand::<Sha256>(&prk, &info, 255 * 32 + 1).is_err());
+    }
+}
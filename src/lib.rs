@@ -8,4 +8,26 @@ pub mod file;
 pub mod hmac;
 mod lazy;
 pub mod base64;
+pub mod hex;
+pub mod percent;
+pub mod data_uri;
+pub mod base32;
+pub mod base58;
+pub mod base62;
+pub mod base85;
 pub mod array;
+pub mod digest;
+pub mod ct;
+pub mod sha3;
+pub mod blake2;
+pub mod blake3;
+pub mod checksum;
+pub mod kdf;
+pub mod password;
+pub mod otp;
+pub mod aead;
+mod curve25519;
+pub mod ed25519;
+pub mod x25519;
+pub mod json;
+pub mod jwt;
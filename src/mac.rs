@@ -41,7 +41,7 @@ pub fn address() -> Option<String> {
 fn is_valid_address(mac: &str) -> bool {
     // windows mac address -> C4-75-AB-75-9C-25
     // linux mac address -> 5A:9A:C3:47:2D:33
-    let parts: Vec<&str> = mac.split(|c| c == ':' || c == '-').collect();
+    let parts: Vec<&str> = mac.split([':', '-']).collect();
     parts.len() == 6 && parts.iter().all(|p| p.len() == 2 && p.chars().all(|c| c.is_ascii_hexdigit()))
 }
 
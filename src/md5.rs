@@ -1,99 +1,61 @@
+use crate::digest::BlockBuffer;
+use crate::hex;
+
+/// MD5 常量
+const K: [u32; 64] = [
+    0xd76aa478, 0xe8c7b756, 0x242070db, 0xc1bdceee, 0xf57c0faf, 0x4787c62a, 0xa8304613,
+    0xfd469501, 0x698098d8, 0x8b44f7af, 0xffff5bb1, 0x895cd7be, 0x6b901122, 0xfd987193,
+    0xa679438e, 0x49b40821, 0xf61e2562, 0xc040b340, 0x265e5a51, 0xe9b6c7aa, 0xd62f105d,
+    0x02441453, 0xd8a1e681, 0xe7d3fbc8, 0x21e1cde6, 0xc33707d6, 0xf4d50d87, 0x455a14ed,
+    0xa9e3e905, 0xfcefa3f8, 0x676f02d9, 0x8d2a4c8a, 0xfffa3942, 0x8771f681, 0x6d9d6122,
+    0xfde5380c, 0xa4beea44, 0x4bdecfa9, 0xf6bb4b60, 0xbebfbc70, 0x289b7ec6, 0xeaa127fa,
+    0xd4ef3085, 0x04881d05, 0xd9d4d039, 0xe6db99e5, 0x1fa27cf8, 0xc4ac5665, 0xf4292244,
+    0x432aff97, 0xab9423a7, 0xfc93a039, 0x655b59c3, 0x8f0ccc92, 0xffeff47d, 0x85845dd1,
+    0x6fa87e4f, 0xfe2ce6e0, 0xa3014314, 0x4e0811a1, 0xf7537e82, 0xbd3af235, 0x2ad7d2bb,
+    0xeb86d391,
+];
+
 /// MD5 结构体
+#[derive(Clone)]
 pub struct Md5 {
-    data: Vec<u8>,       // 原始数据块
-    bit_len: u64,        // 消息总位数
-    state: [u32; 4],     // MD5 状态变量（A, B, C, D）
+    state: [u32; 4],            // MD5 状态变量（A, B, C, D）
+    buffer: BlockBuffer<64>,    // 未满 64 字节的尾部数据
+    len: u64,                   // 消息总字节数
+}
+
+impl Default for Md5 {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Md5 {
     /// 初始化 MD5 状态变量
     pub fn new() -> Self {
         Self {
-            data: Vec::new(),
-            bit_len: 0,
             state: [
                 0x67452301, // A
                 0xefcdab89, // B
                 0x98badcfe, // C
                 0x10325476, // D
             ],
+            buffer: BlockBuffer::new(),
+            len: 0,
         }
     }
 
-    /// 更新 MD5 数据块
+    /// 更新 MD5 数据块，完整的 64 字节块直接从输入中处理
     pub fn update(&mut self, input: &[u8]) {
-        self.data.extend_from_slice(input);
-        self.bit_len += (input.len() as u64) * 8;
-        while self.data.len() >= 64 {
-            let block: [u8; 64] = self.data[..64].try_into().unwrap();
-            self.process_block(&block);
-            self.data.drain(..64);
-        }
-    }
-
-    /// 处理 64 字节块
-    fn process_block(&mut self, block: &[u8; 64]) {
-        const S: [[u32; 4]; 4] = [
-            [7, 12, 17, 22], [5, 9, 14, 20], [4, 11, 16, 23], [6, 10, 15, 21],
-        ];
-        const K: [u32; 64] = [
-            0xd76aa478, 0xe8c7b756, 0x242070db, 0xc1bdceee, 0xf57c0faf, 0x4787c62a, 0xa8304613,
-            0xfd469501, 0x698098d8, 0x8b44f7af, 0xffff5bb1, 0x895cd7be, 0x6b901122, 0xfd987193,
-            0xa679438e, 0x49b40821, 0xf61e2562, 0xc040b340, 0x265e5a51, 0xe9b6c7aa, 0xd62f105d,
-            0x02441453, 0xd8a1e681, 0xe7d3fbc8, 0x21e1cde6, 0xc33707d6, 0xf4d50d87, 0x455a14ed,
-            0xa9e3e905, 0xfcefa3f8, 0x676f02d9, 0x8d2a4c8a, 0xfffa3942, 0x8771f681, 0x6d9d6122,
-            0xfde5380c, 0xa4beea44, 0x4bdecfa9, 0xf6bb4b60, 0xbebfbc70, 0x289b7ec6, 0xeaa127fa,
-            0xd4ef3085, 0x04881d05, 0xd9d4d039, 0xe6db99e5, 0x1fa27cf8, 0xc4ac5665, 0xf4292244,
-            0x432aff97, 0xab9423a7, 0xfc93a039, 0x655b59c3, 0x8f0ccc92, 0xffeff47d, 0x85845dd1,
-            0x6fa87e4f, 0xfe2ce6e0, 0xa3014314, 0x4e0811a1, 0xf7537e82, 0xbd3af235, 0x2ad7d2bb,
-            0xeb86d391,
-        ];
-
-        let mut a = self.state[0];
-        let mut b = self.state[1];
-        let mut c = self.state[2];
-        let mut d = self.state[3];
-
-        let mut m = [0u32; 16];
-        for (i, chunk) in block.chunks(4).enumerate() {
-            m[i] = u32::from_le_bytes(chunk.try_into().unwrap());
-        }
-
-        for i in 0..64 {
-            let (f, g) = match i {
-                0..=15 => ((b & c) | (!b & d), i),
-                16..=31 => ((d & b) | (!d & c), (5 * i + 1) % 16),
-                32..=47 => (b ^ c ^ d, (3 * i + 5) % 16),
-                48..=63 => (c ^ (b | !d), (7 * i) % 16),
-                _ => unreachable!(),
-            };
-
-            let temp = d;
-            d = c;
-            c = b;
-            b = b.wrapping_add(
-                a.wrapping_add(f)
-                    .wrapping_add(K[i])
-                    .wrapping_add(m[g])
-                    .rotate_left(S[i / 16][i % 4]),
-            );
-            a = temp;
-        }
-
-        self.state[0] = self.state[0].wrapping_add(a);
-        self.state[1] = self.state[1].wrapping_add(b);
-        self.state[2] = self.state[2].wrapping_add(c);
-        self.state[3] = self.state[3].wrapping_add(d);
+        self.len = self.len.wrapping_add(input.len() as u64);
+        let state = &mut self.state;
+        self.buffer.update(input, |block| process_block(state, block));
     }
 
     /// 计算最终的哈希值
     pub fn finalize(mut self) -> [u8; 16] {
-        let bit_len_bytes = self.bit_len.to_le_bytes();
-        self.update(&[0x80]);
-        while self.data.len() % 64 != 56 {
-            self.update(&[0x00]);
-        }
-        self.update(&bit_len_bytes);
+        let bit_len = self.len.wrapping_mul(8);
+        let state = &mut self.state;
+        self.buffer.pad(&bit_len.to_le_bytes(), |block| process_block(state, block));
 
         let mut hash = [0u8; 16];
         for (i, &val) in self.state.iter().enumerate() {
@@ -103,6 +65,53 @@ impl Md5 {
     }
 }
 
+#[inline(always)]
+fn step(f: u32, a: u32, b: u32, m: u32, k: u32, s: u32) -> u32 {
+    a.wrapping_add(f).wrapping_add(k).wrapping_add(m).rotate_left(s).wrapping_add(b)
+}
+
+/// 处理 64 字节块，四轮各 16 步全部展开
+fn process_block(state: &mut [u32; 4], block: &[u8; 64]) {
+    let mut m = [0u32; 16];
+    for (i, chunk) in block.chunks_exact(4).enumerate() {
+        m[i] = u32::from_le_bytes(chunk.try_into().unwrap());
+    }
+
+    let [mut a, mut b, mut c, mut d] = *state;
+
+    for i in (0..16).step_by(4) {
+        a = step((b & c) | (!b & d), a, b, m[i], K[i], 7);
+        d = step((a & b) | (!a & c), d, a, m[i + 1], K[i + 1], 12);
+        c = step((d & a) | (!d & b), c, d, m[i + 2], K[i + 2], 17);
+        b = step((c & d) | (!c & a), b, c, m[i + 3], K[i + 3], 22);
+    }
+    for i in (16..32).step_by(4) {
+        a = step((d & b) | (!d & c), a, b, m[(5 * i + 1) % 16], K[i], 5);
+        d = step((c & a) | (!c & b), d, a, m[(5 * i + 6) % 16], K[i + 1], 9);
+        c = step((b & d) | (!b & a), c, d, m[(5 * i + 11) % 16], K[i + 2], 14);
+        b = step((a & c) | (!a & d), b, c, m[(5 * i + 16) % 16], K[i + 3], 20);
+    }
+    for i in (32..48).step_by(4) {
+        a = step(b ^ c ^ d, a, b, m[(3 * i + 5) % 16], K[i], 4);
+        d = step(a ^ b ^ c, d, a, m[(3 * i + 8) % 16], K[i + 1], 11);
+        c = step(d ^ a ^ b, c, d, m[(3 * i + 11) % 16], K[i + 2], 16);
+        b = step(c ^ d ^ a, b, c, m[(3 * i + 14) % 16], K[i + 3], 23);
+    }
+    for i in (48..64).step_by(4) {
+        a = step(c ^ (b | !d), a, b, m[(7 * i) % 16], K[i], 6);
+        d = step(b ^ (a | !c), d, a, m[(7 * i + 7) % 16], K[i + 1], 10);
+        c = step(a ^ (d | !b), c, d, m[(7 * i + 14) % 16], K[i + 2], 15);
+        b = step(d ^ (c | !a), b, c, m[(7 * i + 21) % 16], K[i + 3], 21);
+    }
+
+    state[0] = state[0].wrapping_add(a);
+    state[1] = state[1].wrapping_add(b);
+    state[2] = state[2].wrapping_add(c);
+    state[3] = state[3].wrapping_add(d);
+}
+
+crate::digest::impl_digest!(Md5, 16, 64);
+
 /// Generate md5 string
 ///
 /// # Example
@@ -117,8 +126,7 @@ where
 {
     let mut md5 = Md5::new();
     md5.update(input.as_ref());
-    let digest = md5.finalize();
-    digest.iter().map(|byte| format!("{:02x}", byte)).collect()
+    hex::encode(md5.finalize())
 }
 
 /// Generate md5 string with iteration (迭代多次求md5)
@@ -137,7 +145,7 @@ where
     for _ in 0..iterations {
         let mut md5 = Md5::new();
         md5.update(&current_input);
-        current_input = md5.finalize().iter().map(|byte| format!("{:02x}", byte)).collect::<String>().into_bytes();
+        current_input = hex::encode(md5.finalize()).into_bytes();
     }
     String::from_utf8(current_input).unwrap()
 }
@@ -200,4 +208,13 @@ mod tests {
         );
     }
 
-}
\ No newline at end of file
+    #[test]
+    fn test_md5_streaming() {
+        crate::digest::assert_streaming::<Md5>();
+        assert_eq!(str(crate::digest::test_data(1000)), "a24f1e3ef66950e1327f210e3997ba2c");
+        // 长度跨越填充边界
+        assert_eq!(str(vec![b'a'; 55]), "ef1772b6dff9a122358552954ad0df65");
+        assert_eq!(str(vec![b'a'; 56]), "3b0c8ac703f828b04c6c197006d17218");
+        assert_eq!(str(vec![b'a'; 64]), "014842d480b571495a4a0363793f7367");
+    }
+}
@@ -0,0 +1,298 @@
+use std::io;
+use crate::hmac::{self, Sha1, Sha256, Sha512};
+use crate::percent::{self, EncodeSet};
+use crate::{base32, ct, random, timestamp};
+
+/// HMAC 哈希算法
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Algorithm {
+    Sha1,
+    Sha256,
+    Sha512,
+}
+
+impl Algorithm {
+    fn sign(&self, key: &[u8], counter: u64) -> Vec<u8> {
+        let message = counter.to_be_bytes();
+        match self {
+            Algorithm::Sha1 => hmac::sign::<Sha1, _, _>(key, message),
+            Algorithm::Sha256 => hmac::sign::<Sha256, _, _>(key, message),
+            Algorithm::Sha512 => hmac::sign::<Sha512, _, _>(key, message),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Algorithm::Sha1 => "SHA1",
+            Algorithm::Sha256 => "SHA256",
+            Algorithm::Sha512 => "SHA512",
+        }
+    }
+}
+
+/// HOTP (RFC 4226)
+///
+/// `digits` 取值 6 ~ 8，超出范围时返回错误
+///
+/// # Example
+/// ```rust
+/// use light_tool::otp::{self, Algorithm};
+/// assert_eq!(otp::hotp(b"12345678901234567890", 0, 6, Algorithm::Sha1).unwrap(), "755224");
+/// assert!(otp::hotp(b"12345678901234567890", 0, 9, Algorithm::Sha1).is_err())
+/// ```
+pub fn hotp(secret: &[u8], counter: u64, digits: u32, algorithm: Algorithm) -> Result<String, String> {
+    check_digits(digits)?;
+    Ok(truncate(secret, counter, digits, algorithm))
+}
+
+fn check_digits(digits: u32) -> Result<(), String> {
+    if !(6..=8).contains(&digits) {
+        return Err(format!("Invalid OTP digits: {}, expected 6 ~ 8", digits));
+    }
+    Ok(())
+}
+
+/// 计算 `digits` 位口令，调用方保证 `digits` 已校验
+fn truncate(secret: &[u8], counter: u64, digits: u32, algorithm: Algorithm) -> String {
+    // 动态截断：取最后一个字节的低 4 位作为偏移
+    let mac = algorithm.sign(secret, counter);
+    let offset = (mac[mac.len() - 1] & 0x0f) as usize;
+    let code = u32::from_be_bytes(mac[offset..offset + 4].try_into().unwrap()) & 0x7fffffff;
+    format!("{:0width$}", code % 10u32.pow(digits), width = digits as usize)
+}
+
+/// 校验 HOTP，在 `[counter, counter + window]` 内查找匹配的计数器，返回匹配的计数器；`digits` 不在 6 ~ 8 时返回 `None`
+///
+/// # Example
+/// ```rust
+/// use light_tool::otp::{self, Algorithm};
+/// assert_eq!(otp::verify_hotp(b"12345678901234567890", "969429", 0, 5, 6, Algorithm::Sha1), Some(3))
+/// ```
+pub fn verify_hotp(secret: &[u8], code: &str, counter: u64, window: u64, digits: u32, algorithm: Algorithm) -> Option<u64> {
+    check_digits(digits).ok()?;
+    (counter..=counter.saturating_add(window))
+        .find(|&c| ct::eq(truncate(secret, c, digits, algorithm), code))
+}
+
+/// TOTP (RFC 6238)
+///
+/// 参数只能通过 `with_*` 方法修改并在修改时校验，生成与校验口令时不会 panic
+///
+/// # Example
+/// ```rust
+/// use light_tool::otp::{Algorithm, Totp};
+/// let totp = Totp::new(Totp::generate_secret().unwrap());
+/// let code = totp.generate();
+/// assert!(totp.verify(&code));
+/// println!("{}", totp.uri("LightTool", "alice@example.com"));
+///
+/// let totp = totp.with_digits(8).unwrap().with_step(60).unwrap().with_algorithm(Algorithm::Sha256);
+/// assert_eq!(totp.generate().len(), 8);
+/// assert!(totp.clone().with_digits(9).is_err());
+/// assert!(totp.with_step(0).is_err())
+/// ```
+#[derive(Clone, Debug)]
+pub struct Totp {
+    /// 共享密钥
+    secret: Vec<u8>,
+    /// 口令位数，6 ~ 8
+    digits: u32,
+    /// 时间步长（秒），大于 0
+    step: u64,
+    /// 校验时前后允许偏移的时间步数，用于容忍时钟误差
+    skew: u64,
+    /// HMAC 哈希算法
+    algorithm: Algorithm,
+}
+
+impl Totp {
+    /// 使用默认参数：6 位、30 秒、允许前后 1 个时间步、SHA1（兼容主流验证器应用）
+    pub fn new(secret: Vec<u8>) -> Self {
+        Totp { secret, digits: 6, step: 30, skew: 1, algorithm: Algorithm::Sha1 }
+    }
+
+    /// 设置口令位数，取值 6 ~ 8
+    pub fn with_digits(mut self, digits: u32) -> Result<Self, String> {
+        check_digits(digits)?;
+        self.digits = digits;
+        Ok(self)
+    }
+
+    /// 设置时间步长（秒），必须大于 0
+    pub fn with_step(mut self, step: u64) -> Result<Self, String> {
+        if step == 0 {
+            return Err("Invalid TOTP step: must be greater than 0".to_string());
+        }
+        self.step = step;
+        Ok(self)
+    }
+
+    /// 设置校验时前后允许偏移的时间步数
+    pub fn with_skew(mut self, skew: u64) -> Self {
+        self.skew = skew;
+        self
+    }
+
+    /// 设置 HMAC 哈希算法
+    pub fn with_algorithm(mut self, algorithm: Algorithm) -> Self {
+        self.algorithm = algorithm;
+        self
+    }
+
+    /// 共享密钥
+    pub fn secret(&self) -> &[u8] {
+        &self.secret
+    }
+
+    /// 口令位数
+    pub fn digits(&self) -> u32 {
+        self.digits
+    }
+
+    /// 时间步长（秒）
+    pub fn step(&self) -> u64 {
+        self.step
+    }
+
+    /// 校验时前后允许偏移的时间步数
+    pub fn skew(&self) -> u64 {
+        self.skew
+    }
+
+    /// HMAC 哈希算法
+    pub fn algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+
+    /// 从 Base32 编码的密钥创建，忽略大小写与空格
+    pub fn from_base32(secret: &str) -> Result<Self, String> {
+        let secret = base32::decode_bytes(&secret.replace(' ', "")).map_err(|e| e.to_string())?;
+        Ok(Self::new(secret))
+    }
+
+    /// 使用安全随机数生成 20 字节（160 位）密钥
+    pub fn generate_secret() -> io::Result<Vec<u8>> {
+        random::secure_bytes(20)
+    }
+
+    /// Base32 编码的密钥，供用户手动输入
+    pub fn secret_base32(&self) -> String {
+        base32::encode_bytes_unpadded(&self.secret)
+    }
+
+    /// 生成指定时间（秒级时间戳）的口令
+    pub fn generate_at(&self, time: u64) -> String {
+        truncate(&self.secret, time / self.step, self.digits, self.algorithm)
+    }
+
+    /// 生成当前时间的口令
+    pub fn generate(&self) -> String {
+        self.generate_at(timestamp::seconds())
+    }
+
+    /// 校验指定时间的口令，允许前后 `skew` 个时间步
+    pub fn verify_at(&self, code: &str, time: u64) -> bool {
+        let counter = time / self.step;
+        let mut matched = false;
+        for c in counter.saturating_sub(self.skew)..=counter.saturating_add(self.skew) {
+            // 不提前返回，避免泄露匹配的位置
+            matched |= ct::eq(truncate(&self.secret, c, self.digits, self.algorithm), code);
+        }
+        matched
+    }
+
+    /// 校验当前时间的口令
+    pub fn verify(&self, code: &str) -> bool {
+        self.verify_at(code, timestamp::seconds())
+    }
+
+    /// 生成供验证器应用扫码的 `otpauth://` URI
+    pub fn uri(&self, issuer: &str, account: &str) -> String {
+        format!(
+            "otpauth://totp/{}:{}?secret={}&issuer={}&algorithm={}&digits={}&period={}",
+            percent::encode(issuer, &LABEL),
+            percent::encode(account, &LABEL),
+            self.secret_base32(),
+            percent::encode(issuer, &EncodeSet::QUERY),
+            self.algorithm.name(),
+            self.digits,
+            self.step,
+        )
+    }
+}
+
+/// URI 标签的编码集合，`:` 用于分隔发行方与账户名，必须编码
+const LABEL: EncodeSet = EncodeSet::PATH_SEGMENT.add(b':');
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hotp() {
+        // RFC 4226 Appendix D
+        let expected = [
+            "755224", "287082", "359152", "969429", "338314",
+            "254676", "287922", "162583", "399871", "520489",
+        ];
+        for (counter, code) in expected.iter().enumerate() {
+            assert_eq!(hotp(b"12345678901234567890", counter as u64, 6, Algorithm::Sha1).unwrap(), *code);
+        }
+        assert_eq!(verify_hotp(b"12345678901234567890", "520489", 5, 5, 6, Algorithm::Sha1), Some(9));
+        assert_eq!(verify_hotp(b"12345678901234567890", "520489", 0, 5, 6, Algorithm::Sha1), None);
+
+        // 非法位数返回错误而不是 panic
+        assert!(hotp(b"12345678901234567890", 0, 5, Algorithm::Sha1).is_err());
+        assert!(hotp(b"12345678901234567890", 0, 9, Algorithm::Sha1).is_err());
+        assert_eq!(verify_hotp(b"12345678901234567890", "4755224", 0, 5, 7, Algorithm::Sha1), Some(0));
+        assert_eq!(verify_hotp(b"12345678901234567890", "755224", 0, 5, 0, Algorithm::Sha1), None);
+    }
+
+    #[test]
+    fn test_totp() {
+        // RFC 6238 Appendix B
+        let cases = [
+            (59, "94287082", "46119246", "90693936"),
+            (1111111109, "07081804", "68084774", "25091201"),
+            (1234567890, "89005924", "91819424", "93441116"),
+            (2000000000, "69279037", "90698825", "38618901"),
+        ];
+        let sha1 = Totp::new(b"12345678901234567890".to_vec()).with_digits(8).unwrap();
+        let sha256 = Totp::new(b"12345678901234567890123456789012".to_vec()).with_digits(8).unwrap().with_algorithm(Algorithm::Sha256);
+        let sha512 = Totp::new(b"1234567890123456789012345678901234567890123456789012345678901234".to_vec()).with_digits(8).unwrap().with_algorithm(Algorithm::Sha512);
+        for (time, code1, code256, code512) in cases {
+            assert_eq!(sha1.generate_at(time), code1);
+            assert_eq!(sha256.generate_at(time), code256);
+            assert_eq!(sha512.generate_at(time), code512);
+        }
+
+        // 时钟漂移窗口
+        assert!(sha1.verify_at("07081804", 1111111109 + 30));
+        assert!(!sha1.verify_at("07081804", 1111111109 + 60));
+        let sha256 = sha256.with_skew(0);
+        let sha512 = sha512.with_skew(2);
+        assert!(!sha256.verify_at("68084774", 1111111109 + 30));
+        assert!(sha512.verify_at("25091201", 1111111109 + 60));
+
+        // 非法参数在设置时返回错误
+        assert!(Totp::new(vec![0; 20]).with_step(0).is_err());
+        assert!(Totp::new(vec![0; 20]).with_digits(5).is_err());
+        assert!(Totp::new(vec![0; 20]).with_digits(9).is_err());
+    }
+
+    #[test]
+    fn test_secret_and_uri() {
+        let totp = Totp::new(b"12345678901234567890".to_vec());
+        assert_eq!(totp.secret_base32(), "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ");
+        assert_eq!(Totp::from_base32("gezd gnbv gy3t qojq gezd gnbv gy3t qojq").unwrap().secret(), totp.secret());
+        assert!(Totp::from_base32("GEZDGNBV1").is_err());
+        assert_eq!(
+            totp.uri("Light Tool", "alice@example.com"),
+            "otpauth://totp/Light%20Tool:alice@example.com?secret=GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ&issuer=Light%20Tool&algorithm=SHA1&digits=6&period=30"
+        );
+
+        let totp = Totp::new(Totp::generate_secret().unwrap());
+        assert_eq!(totp.secret().len(), 20);
+        assert!(totp.verify(&totp.generate()));
+    }
+}
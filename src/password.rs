@@ -0,0 +1,533 @@
+use crate::blake2::Blake2b;
+use crate::{base64, ct, md5, random};
+
+/// Argon2 版本号 0x13
+const VERSION: u32 = 0x13;
+/// Argon2id 类型编号
+const ARGON2ID: u32 = 2;
+/// 每个内存块 1 KiB，即 128 个 u64
+const BLOCK_WORDS: usize = 128;
+/// 每趟分为 4 个切片
+const SYNC_POINTS: usize = 4;
+/// 内存上限 2 GiB（RFC 9106 第一推荐配置）
+const MAX_M_COST: u32 = 2 * 1024 * 1024;
+/// 迭代次数上限
+const MAX_T_COST: u32 = 64;
+
+type Block = [u64; BLOCK_WORDS];
+
+/// Argon2id 参数
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Params {
+    /// 内存大小（KiB），至少为 8 * p_cost，最多 2 GiB
+    pub m_cost: u32,
+    /// 迭代次数，1 ~ 64
+    pub t_cost: u32,
+    /// 并行度（lane 数）
+    pub p_cost: u32,
+    /// 输出长度（字节），至少 4
+    pub output_len: usize,
+}
+
+impl Default for Params {
+    /// OWASP 推荐的最低配置：19 MiB 内存、2 次迭代、并行度 1
+    fn default() -> Self {
+        Params { m_cost: 19456, t_cost: 2, p_cost: 1, output_len: 32 }
+    }
+}
+
+impl Params {
+    fn validate(&self) -> Result<(), String> {
+        if self.p_cost == 0 || self.p_cost > 0xffffff {
+            return Err(format!("invalid parallelism: {}", self.p_cost));
+        }
+        if self.t_cost == 0 || self.t_cost > MAX_T_COST {
+            return Err(format!("iterations must be 1 ~ {}: {}", MAX_T_COST, self.t_cost));
+        }
+        if self.m_cost < 8 * self.p_cost || self.m_cost > MAX_M_COST {
+            return Err(format!("memory must be between 8 * p and {} KiB: {}", MAX_M_COST, self.m_cost));
+        }
+        if self.output_len < 4 {
+            return Err(format!("output length too short: {}", self.output_len));
+        }
+        Ok(())
+    }
+}
+
+/// 校验口令时允许的参数上限
+///
+/// 存储的哈希可能来自不可信的来源，参数超出上限时直接拒绝，不会分配内存，避免登录接口被用于消耗资源
+///
+/// # Example
+/// ```rust
+/// use light_tool::password::{self, Limits};
+/// let stored = "$argon2id$v=19$m=64,t=2,p=1$c29tZXNhbHQ$FqGkmHNGCd0BRW2kBt6fPZ2pPmyGwwChL8FGUhTOSSI";
+/// assert!(password::verify_with_limits("password", stored, &Limits { m_cost: 1024, t_cost: 4 }));
+/// assert!(!password::verify_with_limits("password", stored, &Limits { m_cost: 32, t_cost: 4 }))
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Limits {
+    /// 内存上限（KiB）
+    pub m_cost: u32,
+    /// 迭代次数上限
+    pub t_cost: u32,
+}
+
+impl Default for Limits {
+    /// 256 MiB 内存、16 次迭代
+    fn default() -> Self {
+        Limits { m_cost: 256 * 1024, t_cost: 16 }
+    }
+}
+
+impl Limits {
+    fn check(&self, params: &Params) -> Result<(), String> {
+        if params.m_cost > self.m_cost || params.t_cost > self.t_cost {
+            return Err(format!("parameters exceed limits: m={}, t={}", params.m_cost, params.t_cost));
+        }
+        Ok(())
+    }
+}
+
+/// 变长哈希 H'
+fn blake2b_long(input: &[&[u8]], out_len: usize) -> Vec<u8> {
+    let len_bytes = (out_len as u32).to_le_bytes();
+    if out_len <= 64 {
+        let mut hasher = Blake2b::with_params(out_len, &[], &[], &[]);
+        hasher.update(&len_bytes);
+        for part in input {
+            hasher.update(part);
+        }
+        return hasher.finalize();
+    }
+
+    // 每次输出上一个 64 字节哈希的前 32 字节，最后一段按剩余长度输出
+    let mut hasher = Blake2b::new();
+    hasher.update(&len_bytes);
+    for part in input {
+        hasher.update(part);
+    }
+    let mut v = hasher.finalize();
+    let mut output = Vec::with_capacity(out_len);
+    while out_len - output.len() > 64 {
+        output.extend_from_slice(&v[..32]);
+        let remaining = out_len - output.len();
+        let mut hasher = Blake2b::with_params(remaining.min(64), &[], &[], &[]);
+        hasher.update(&v);
+        v = hasher.finalize();
+    }
+    output.extend_from_slice(&v);
+    output
+}
+
+fn block_from_bytes(bytes: &[u8]) -> Block {
+    let mut block = [0u64; BLOCK_WORDS];
+    for (word, chunk) in block.iter_mut().zip(bytes.chunks(8)) {
+        *word = u64::from_le_bytes(chunk.try_into().unwrap());
+    }
+    block
+}
+
+/// BlaMka 轮函数中的 G
+fn gb(v: &mut Block, a: usize, b: usize, c: usize, d: usize) {
+    let mul = |x: u64, y: u64| 2u64.wrapping_mul(x & 0xffffffff).wrapping_mul(y & 0xffffffff);
+    v[a] = v[a].wrapping_add(v[b]).wrapping_add(mul(v[a], v[b]));
+    v[d] = (v[d] ^ v[a]).rotate_right(32);
+    v[c] = v[c].wrapping_add(v[d]).wrapping_add(mul(v[c], v[d]));
+    v[b] = (v[b] ^ v[c]).rotate_right(24);
+    v[a] = v[a].wrapping_add(v[b]).wrapping_add(mul(v[a], v[b]));
+    v[d] = (v[d] ^ v[a]).rotate_right(16);
+    v[c] = v[c].wrapping_add(v[d]).wrapping_add(mul(v[c], v[d]));
+    v[b] = (v[b] ^ v[c]).rotate_right(63);
+}
+
+/// 对 16 个字（下标由 `idx` 给出）执行一次置换 P
+fn permute(v: &mut Block, idx: [usize; 16]) {
+    let [i0, i1, i2, i3, i4, i5, i6, i7, i8, i9, i10, i11, i12, i13, i14, i15] = idx;
+    gb(v, i0, i4, i8, i12);
+    gb(v, i1, i5, i9, i13);
+    gb(v, i2, i6, i10, i14);
+    gb(v, i3, i7, i11, i15);
+    gb(v, i0, i5, i10, i15);
+    gb(v, i1, i6, i11, i12);
+    gb(v, i2, i7, i8, i13);
+    gb(v, i3, i4, i9, i14);
+}
+
+/// 压缩函数 G(X, Y)，`xor` 为真时结果再与 `next` 原值异或（第二趟及之后）
+fn fill_block(prev: &Block, reference: &Block, next: &mut Block, xor: bool) {
+    let mut r = [0u64; BLOCK_WORDS];
+    for i in 0..BLOCK_WORDS {
+        r[i] = prev[i] ^ reference[i];
+    }
+    let mut q = r;
+
+    // 按行：每行 16 个连续的字
+    for row in 0..8 {
+        let base = row * 16;
+        permute(&mut q, std::array::from_fn(|i| base + i));
+    }
+    // 按列：每列由各行中相同位置的两个字组成
+    for col in 0..8 {
+        permute(&mut q, std::array::from_fn(|i| 16 * (i / 2) + 2 * col + (i % 2)));
+    }
+
+    for i in 0..BLOCK_WORDS {
+        next[i] = if xor { next[i] ^ q[i] ^ r[i] } else { q[i] ^ r[i] };
+    }
+}
+
+/// Argon2id 核心，`secret` 与 `ad` 为 RFC 9106 中的可选密钥与附加数据
+fn argon2id_raw(password: &[u8], salt: &[u8], secret: &[u8], ad: &[u8], params: &Params) -> Vec<u8> {
+    let lanes = params.p_cost as usize;
+    let segment_len = params.m_cost as usize / (SYNC_POINTS * lanes);
+    let lane_len = segment_len * SYNC_POINTS;
+    let block_count = lane_len * lanes;
+
+    let h0 = {
+        let mut hasher = Blake2b::new();
+        for value in [params.p_cost, params.output_len as u32, params.m_cost, params.t_cost, VERSION, ARGON2ID] {
+            hasher.update(&value.to_le_bytes());
+        }
+        for data in [password, salt, secret, ad] {
+            hasher.update(&(data.len() as u32).to_le_bytes());
+            hasher.update(data);
+        }
+        hasher.finalize()
+    };
+
+    let mut memory = vec![[0u64; BLOCK_WORDS]; block_count];
+    for lane in 0..lanes {
+        for i in 0..2u32 {
+            let bytes = blake2b_long(&[&h0, &i.to_le_bytes(), &(lane as u32).to_le_bytes()], 1024);
+            memory[lane * lane_len + i as usize] = block_from_bytes(&bytes);
+        }
+    }
+
+    let zero = [0u64; BLOCK_WORDS];
+    for pass in 0..params.t_cost as usize {
+        for slice in 0..SYNC_POINTS {
+            for lane in 0..lanes {
+                // 第一趟的前两个切片使用与数据无关的寻址，抵抗侧信道
+                let data_independent = pass == 0 && slice < 2;
+                let mut input = [0u64; BLOCK_WORDS];
+                let mut addresses = [0u64; BLOCK_WORDS];
+                if data_independent {
+                    input[..6].copy_from_slice(&[
+                        pass as u64, lane as u64, slice as u64, block_count as u64,
+                        params.t_cost as u64, ARGON2ID as u64,
+                    ]);
+                }
+                let next_addresses = |input: &mut Block, addresses: &mut Block| {
+                    input[6] += 1;
+                    let mut tmp = [0u64; BLOCK_WORDS];
+                    fill_block(&zero, input, &mut tmp, false);
+                    fill_block(&zero, &tmp, addresses, false);
+                };
+
+                let start = if pass == 0 && slice == 0 {
+                    if data_independent {
+                        next_addresses(&mut input, &mut addresses);
+                    }
+                    2
+                } else {
+                    0
+                };
+
+                for index in start..segment_len {
+                    let column = slice * segment_len + index;
+                    let current = lane * lane_len + column;
+                    let prev = if column == 0 { current + lane_len - 1 } else { current - 1 };
+
+                    let pseudo_rand = if data_independent {
+                        if index % BLOCK_WORDS == 0 {
+                            next_addresses(&mut input, &mut addresses);
+                        }
+                        addresses[index % BLOCK_WORDS]
+                    } else {
+                        memory[prev][0]
+                    };
+
+                    let ref_lane = if pass == 0 && slice == 0 {
+                        lane
+                    } else {
+                        ((pseudo_rand >> 32) as usize) % lanes
+                    };
+                    let same_lane = ref_lane == lane;
+
+                    // 可引用区域的大小
+                    let area_size = if pass == 0 {
+                        if slice == 0 {
+                            index - 1
+                        } else if same_lane {
+                            slice * segment_len + index - 1
+                        } else {
+                            slice * segment_len - if index == 0 { 1 } else { 0 }
+                        }
+                    } else if same_lane {
+                        lane_len - segment_len + index - 1
+                    } else {
+                        lane_len - segment_len - if index == 0 { 1 } else { 0 }
+                    };
+
+                    let j1 = pseudo_rand & 0xffffffff;
+                    let x = (j1 * j1) >> 32;
+                    let y = (area_size as u64 * x) >> 32;
+                    let relative = area_size - 1 - y as usize;
+                    let start_position = if pass != 0 && slice != SYNC_POINTS - 1 {
+                        (slice + 1) * segment_len
+                    } else {
+                        0
+                    };
+                    let ref_index = ref_lane * lane_len + (start_position + relative) % lane_len;
+
+                    let prev_block = memory[prev];
+                    let ref_block = memory[ref_index];
+                    fill_block(&prev_block, &ref_block, &mut memory[current], pass > 0);
+                }
+            }
+        }
+    }
+
+    let mut last = memory[lane_len - 1];
+    for lane in 1..lanes {
+        for (word, value) in last.iter_mut().zip(memory[lane * lane_len + lane_len - 1].iter()) {
+            *word ^= value;
+        }
+    }
+    let bytes: Vec<u8> = last.iter().flat_map(|word| word.to_le_bytes()).collect();
+    blake2b_long(&[&bytes], params.output_len)
+}
+
+/// Argon2id (RFC 9106) 口令哈希
+///
+/// # Example
+/// ```rust
+/// use light_tool::password::{self, Params};
+/// let params = Params { m_cost: 64, t_cost: 2, p_cost: 1, output_len: 32 };
+/// println!("argon2id: {:?}", password::argon2id(b"password", b"somesalt", &params).unwrap())
+/// ```
+pub fn argon2id(password: &[u8], salt: &[u8], params: &Params) -> Result<Vec<u8>, String> {
+    params.validate()?;
+    if salt.len() < 8 {
+        return Err(format!("salt too short: {}", salt.len()));
+    }
+    Ok(argon2id_raw(password, salt, &[], &[], params))
+}
+
+/// 使用默认参数与随机盐值计算口令哈希，返回 PHC 格式字符串
+///
+/// # Example
+/// ```rust
+/// use light_tool::password;
+/// let stored = password::hash("123456").unwrap();
+/// // $argon2id$v=19$m=19456,t=2,p=1$...$...
+/// assert!(password::verify("123456", &stored))
+/// ```
+pub fn hash<T: AsRef<[u8]>>(plain: T) -> Result<String, String> {
+    hash_with_params(plain, &Params::default())
+}
+
+/// 使用指定参数与随机盐值计算口令哈希，返回 PHC 格式字符串
+pub fn hash_with_params<T: AsRef<[u8]>>(plain: T, params: &Params) -> Result<String, String> {
+    let salt = random::secure_bytes(16).map_err(|e| e.to_string())?;
+    let hash = argon2id(plain.as_ref(), &salt, params)?;
+    Ok(format!(
+        "$argon2id$v={}$m={},t={},p={}${}${}",
+        VERSION,
+        params.m_cost,
+        params.t_cost,
+        params.p_cost,
+        base64::STANDARD_NO_PAD.encode(salt),
+        base64::STANDARD_NO_PAD.encode(hash),
+    ))
+}
+
+/// 解析后的 PHC 字符串
+struct Phc {
+    params: Params,
+    salt: Vec<u8>,
+    hash: Vec<u8>,
+}
+
+fn parse_phc(stored: &str) -> Option<Phc> {
+    let mut parts = stored.split('$');
+    if !parts.next()?.is_empty() || parts.next()? != "argon2id" {
+        return None;
+    }
+    if parts.next()? != format!("v={}", VERSION) {
+        return None;
+    }
+
+    let (mut m_cost, mut t_cost, mut p_cost) = (None, None, None);
+    for param in parts.next()?.split(',') {
+        let (key, value) = param.split_once('=')?;
+        let value = value.parse::<u32>().ok()?;
+        match key {
+            "m" => m_cost = Some(value),
+            "t" => t_cost = Some(value),
+            "p" => p_cost = Some(value),
+            _ => return None,
+        }
+    }
+
+    // PHC 格式使用不带填充的 Base64
+    let salt = base64::STANDARD_NO_PAD.decode(parts.next()?).ok()?;
+    let hash = base64::STANDARD_NO_PAD.decode(parts.next()?).ok()?;
+    if parts.next().is_some() {
+        return None;
+    }
+
+    let params = Params { m_cost: m_cost?, t_cost: t_cost?, p_cost: p_cost?, output_len: hash.len() };
+    params.validate().ok()?;
+    Some(Phc { params, salt, hash })
+}
+
+/// 校验口令与 PHC 格式的哈希，以常量时间比较，参数超出 `Limits::default()` 时返回 false
+///
+/// # Example
+/// ```rust
+/// use light_tool::password;
+/// let stored = "$argon2id$v=19$m=64,t=2,p=1$c29tZXNhbHQ$FqGkmHNGCd0BRW2kBt6fPZ2pPmyGwwChL8FGUhTOSSI";
+/// assert!(password::verify("password", stored))
+/// ```
+pub fn verify<T: AsRef<[u8]>>(plain: T, stored: &str) -> bool {
+    verify_with_limits(plain, stored, &Limits::default())
+}
+
+/// 校验口令与 PHC 格式的哈希，参数超出 `limits` 时返回 false
+pub fn verify_with_limits<T: AsRef<[u8]>>(plain: T, stored: &str, limits: &Limits) -> bool {
+    match parse_phc(stored).filter(|phc| limits.check(&phc.params).is_ok()) {
+        Some(phc) => match argon2id(plain.as_ref(), &phc.salt, &phc.params) {
+            Ok(hash) => ct::eq(&hash, &phc.hash),
+            Err(_) => false,
+        },
+        None => false,
+    }
+}
+
+/// 判断存储的哈希是否需要重新计算：旧的 md5 哈希、无法识别的格式或参数弱于默认值
+///
+/// # Example
+/// ```rust
+/// use light_tool::password;
+/// assert!(password::needs_rehash("e10adc3949ba59abbe56e057f20f883e"))
+/// ```
+pub fn needs_rehash(stored: &str) -> bool {
+    match parse_phc(stored) {
+        Some(phc) => {
+            let default = Params::default();
+            phc.params.m_cost < default.m_cost
+                || phc.params.t_cost < default.t_cost
+                || phc.params.output_len < default.output_len
+                || phc.salt.len() < 16
+        }
+        None => true,
+    }
+}
+
+/// 判断是否为 `md5::salt` 生成的旧哈希（32 位十六进制字符串）
+pub fn is_legacy_md5(stored: &str) -> bool {
+    stored.len() == 32 && stored.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+/// 校验 `md5::salt(plain, salt)` 生成的旧哈希，以常量时间比较
+///
+/// # Example
+/// ```rust
+/// use light_tool::password;
+/// assert!(password::verify_legacy_md5("123", "456", "e10adc3949ba59abbe56e057f20f883e"))
+/// ```
+pub fn verify_legacy_md5(plain: &str, salt: &str, stored: &str) -> bool {
+    is_legacy_md5(stored)
+        && ct::eq(md5::salt(plain, salt), stored.to_ascii_lowercase())
+}
+
+/// 旧的 md5 哈希校验通过时，返回新的 Argon2id PHC 字符串用于替换存储；校验失败返回 `None`
+///
+/// # Example
+/// ```rust
+/// use light_tool::password;
+/// if let Some(upgraded) = password::upgrade_legacy_md5("123", "456", "e10adc3949ba59abbe56e057f20f883e").unwrap() {
+///     assert!(password::verify("123", &upgraded))
+/// }
+/// ```
+pub fn upgrade_legacy_md5(plain: &str, salt: &str, stored: &str) -> Result<Option<String>, String> {
+    if !verify_legacy_md5(plain, salt, stored) {
+        return Ok(None);
+    }
+    hash(plain).map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hex::encode as hex;
+
+    #[test]
+    fn test_argon2id() {
+        // RFC 9106 5.3
+        let params = Params { m_cost: 32, t_cost: 3, p_cost: 4, output_len: 32 };
+        assert_eq!(
+            hex(argon2id_raw(&[0x01; 32], &[0x02; 16], &[0x03; 8], &[0x04; 12], &params)),
+            "0d640df58d78766c08c037a34a8b53c9d01ef0452d75b65eb52520e96b01e659"
+        );
+
+        let params = Params { m_cost: 64, t_cost: 2, p_cost: 1, output_len: 32 };
+        assert_eq!(
+            hex(argon2id(b"password", b"somesalt", &params).unwrap()),
+            "16a1a498734609dd01456da406de9f3d9da93e6c86c300a12fc1465214ce4922"
+        );
+
+        // 输出长于 64 字节时走变长哈希
+        let params = Params { m_cost: 256, t_cost: 3, p_cost: 2, output_len: 40 };
+        assert_eq!(
+            hex(argon2id(b"password", b"saltsaltsaltsalt", &params).unwrap()),
+            "a9c76f4cb1bf09d3aff5574fd1cb235de73b6a3e88f54bb84cd40ce42025ccff25fe26d6cd146687"
+        );
+
+        assert!(argon2id(b"password", b"short", &params).is_err());
+    }
+
+    #[test]
+    fn test_hash_verify() {
+        let params = Params { m_cost: 256, t_cost: 1, p_cost: 1, output_len: 32 };
+        let stored = hash_with_params("123456", &params).unwrap();
+        assert!(stored.starts_with("$argon2id$v=19$m=256,t=1,p=1$"));
+        assert!(verify("123456", &stored));
+        assert!(!verify("1234567", &stored));
+        assert!(needs_rehash(&stored));
+
+        let stored = "$argon2id$v=19$m=64,t=2,p=1$c29tZXNhbHQ$FqGkmHNGCd0BRW2kBt6fPZ2pPmyGwwChL8FGUhTOSSI";
+        assert!(verify("password", stored));
+        assert!(!verify("password", &stored.replace("t=2", "t=3")));
+        assert!(!verify("password", "$argon2i$v=19$m=64,t=2,p=1$c29tZXNhbHQ$FqGkmHNGCd0BRW2kBt6fPZ2pPmyGwwChL8FGUhTOSSI"));
+        assert!(!verify("password", "not a hash"));
+
+        // 超出上限的参数直接拒绝，不会尝试分配内存
+        assert!(!verify("password", &stored.replace("m=64", "m=4294967295")));
+        assert!(!verify("password", &stored.replace("t=2", "t=4294967295")));
+        assert!(hash_with_params("123456", &Params { m_cost: MAX_M_COST + 1, ..params }).is_err());
+
+        // 可以生成但超出校验上限的参数同样直接拒绝
+        let limits = Limits::default();
+        let huge = parse_phc(&stored.replace("m=64", "m=2097152")).unwrap();
+        assert!(limits.check(&huge.params).is_err());
+        assert!(!verify("password", &stored.replace("m=64", "m=2097152")));
+        assert!(!verify("password", &stored.replace("t=2", "t=17")));
+        assert!(limits.check(&Params { t_cost: 16, m_cost: 256 * 1024, ..params }).is_ok());
+        assert!(!verify_with_limits("password", stored, &Limits { m_cost: 63, t_cost: 2 }));
+        assert!(verify_with_limits("password", stored, &Limits { m_cost: 64, t_cost: 2 }));
+    }
+
+    #[test]
+    fn test_legacy_md5() {
+        let stored = md5::salt("123", "456");
+        assert!(is_legacy_md5(&stored));
+        assert!(needs_rehash(&stored));
+        assert!(verify_legacy_md5("123", "456", &stored));
+        assert!(verify_legacy_md5("123", "456", &stored.to_uppercase()));
+        assert!(!verify_legacy_md5("124", "456", &stored));
+        assert_eq!(upgrade_legacy_md5("124", "456", &stored).unwrap(), None);
+    }
+}
@@ -0,0 +1,197 @@
+pub use crate::base64::DecodeError;
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789ABCDEF";
+
+/// 百分号编码时需要转义的字符集合
+///
+/// RFC 3986 非保留字符 `A-Z a-z 0-9 - . _ ~` 默认不编码，非 ASCII 字节总是编码
+///
+/// # Example
+/// ```rust
+/// use light_tool::percent::{self, EncodeSet};
+/// // 在路径段的基础上额外编码 `:`
+/// const LABEL: EncodeSet = EncodeSet::PATH_SEGMENT.add(b':');
+/// assert_eq!(percent::encode("a:b@c", &LABEL), "a%3Ab@c")
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EncodeSet {
+    /// 第 i 位为 1 表示 ASCII 字符 i 需要编码
+    mask: u128,
+    /// 空格编码为 `+`
+    space_as_plus: bool,
+}
+
+impl EncodeSet {
+    /// 只保留非保留字符，适合编码任意 URL 组件
+    pub const COMPONENT: EncodeSet = EncodeSet::keeping(b"");
+    /// 路径段，保留子分隔符与 `:` `@`，编码 `/` `?` `#`
+    pub const PATH_SEGMENT: EncodeSet = EncodeSet::keeping(b"!$&'()*+,;=:@");
+    /// 查询参数的键或值，编码 `&` `=` `+` `;` `#`
+    pub const QUERY: EncodeSet = EncodeSet::keeping(b"!$'()*,:@/?");
+    /// URL 中的用户名或密码，编码 `:` `@` `/`
+    pub const USERINFO: EncodeSet = EncodeSet::keeping(b"!$&'()*+,;=");
+    /// `application/x-www-form-urlencoded`，只保留字母数字与 `*-._`，空格编码为 `+`
+    pub const FORM: EncodeSet = EncodeSet { space_as_plus: true, ..EncodeSet::keeping(b"*").add(b'~') };
+    /// 已拼接好的 URL，保留全部保留字符与 `%`，只编码空格、控制字符、引号、尖括号等 URL 中不允许出现的字符与非 ASCII 字节
+    pub const URI: EncodeSet = EncodeSet::keeping(b"!#$%&'()*+,/:;=?@[]");
+
+    /// 在非保留字符之外额外保留 `extra` 中的字符
+    const fn keeping(extra: &[u8]) -> EncodeSet {
+        let mut set = EncodeSet { mask: !0, space_as_plus: false };
+        let mut byte = 0;
+        while byte < 128 {
+            if (byte as u8).is_ascii_alphanumeric() || matches!(byte as u8, b'-' | b'.' | b'_' | b'~') {
+                set = set.remove(byte as u8);
+            }
+            byte += 1;
+        }
+        let mut i = 0;
+        while i < extra.len() {
+            set = set.remove(extra[i]);
+            i += 1;
+        }
+        set
+    }
+
+    /// 额外编码某个 ASCII 字符
+    pub const fn add(self, byte: u8) -> EncodeSet {
+        assert!(byte < 128, "EncodeSet only contains ASCII characters");
+        EncodeSet { mask: self.mask | 1 << byte, ..self }
+    }
+
+    /// 不再编码某个 ASCII 字符
+    pub const fn remove(self, byte: u8) -> EncodeSet {
+        assert!(byte < 128, "EncodeSet only contains ASCII characters");
+        EncodeSet { mask: self.mask & !(1 << byte), ..self }
+    }
+
+    fn contains(&self, byte: u8) -> bool {
+        byte >= 128 || (self.mask >> byte) & 1 == 1
+    }
+}
+
+/// 百分号编码，字符串按 UTF-8 字节逐个编码
+///
+/// # Example
+/// ```rust
+/// use light_tool::percent::{self, EncodeSet};
+/// assert_eq!(percent::encode("a b&c=中", &EncodeSet::QUERY), "a%20b%26c%3D%E4%B8%AD");
+/// assert_eq!(percent::encode("a b&c", &EncodeSet::FORM), "a+b%26c");
+/// assert_eq!(percent::encode("docs/v1.0 draft", &EncodeSet::PATH_SEGMENT), "docs%2Fv1.0%20draft")
+/// ```
+pub fn encode<T: AsRef<[u8]>>(input: T, set: &EncodeSet) -> String {
+    let input = input.as_ref();
+    let mut output = String::with_capacity(input.len());
+    for &byte in input {
+        if byte == b' ' && set.space_as_plus {
+            output.push('+');
+        } else if set.contains(byte) {
+            output.push('%');
+            output.push(HEX_DIGITS[(byte >> 4) as usize] as char);
+            output.push(HEX_DIGITS[(byte & 0xf) as usize] as char);
+        } else {
+            output.push(byte as char);
+        }
+    }
+    output
+}
+
+/// 百分号解码，结果必须是合法的 UTF-8
+///
+/// # Example
+/// ```rust
+/// use light_tool::percent;
+/// assert_eq!(percent::decode("a%20b%26c%3D%E4%B8%AD").unwrap(), "a b&c=中");
+/// assert!(percent::decode("%FF").is_err())
+/// ```
+pub fn decode(input: &str) -> Result<String, String> {
+    let bytes = decode_bytes(input).map_err(|e| e.to_string())?;
+    String::from_utf8(bytes).map_err(|e| e.to_string())
+}
+
+/// 百分号解码为字节，`+` 原样保留
+///
+/// # Example
+/// ```rust
+/// use light_tool::percent::{self, DecodeError};
+/// assert_eq!(percent::decode_bytes("%FF+").unwrap(), [0xff, b'+']);
+/// assert_eq!(percent::decode_bytes("50%"), Err(DecodeError::InvalidByte { offset: 2, byte: b'%' }));
+/// assert_eq!(percent::decode_bytes("%4g"), Err(DecodeError::InvalidByte { offset: 2, byte: b'g' }))
+/// ```
+pub fn decode_bytes(input: &str) -> Result<Vec<u8>, DecodeError> {
+    decode_with(input, false)
+}
+
+/// 解码 `application/x-www-form-urlencoded` 数据，`+` 解码为空格
+///
+/// # Example
+/// ```rust
+/// use light_tool::percent;
+/// assert_eq!(percent::form_decode("a+b%2Bc").unwrap(), "a b+c")
+/// ```
+pub fn form_decode(input: &str) -> Result<String, String> {
+    let bytes = decode_with(input, true).map_err(|e| e.to_string())?;
+    String::from_utf8(bytes).map_err(|e| e.to_string())
+}
+
+fn decode_with(input: &str, plus_as_space: bool) -> Result<Vec<u8>, DecodeError> {
+    let bytes = input.as_bytes();
+    let mut output = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' => {
+                if i + 2 >= bytes.len() {
+                    return Err(DecodeError::InvalidByte { offset: i, byte: b'%' });
+                }
+                let digit = |offset: usize| {
+                    (bytes[offset] as char)
+                        .to_digit(16)
+                        .ok_or(DecodeError::InvalidByte { offset, byte: bytes[offset] })
+                };
+                output.push((digit(i + 1)? << 4 | digit(i + 2)?) as u8);
+                i += 3;
+            }
+            b'+' if plus_as_space => {
+                output.push(b' ');
+                i += 1;
+            }
+            byte => {
+                output.push(byte);
+                i += 1;
+            }
+        }
+    }
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_sets() {
+        let input = "a b!$&'()*+,;=:@/?#%~中";
+        assert_eq!(encode(input, &EncodeSet::COMPONENT), "a%20b%21%24%26%27%28%29%2A%2B%2C%3B%3D%3A%40%2F%3F%23%25~%E4%B8%AD");
+        assert_eq!(encode(input, &EncodeSet::PATH_SEGMENT), "a%20b!$&'()*+,;=:@%2F%3F%23%25~%E4%B8%AD");
+        assert_eq!(encode(input, &EncodeSet::QUERY), "a%20b!$%26'()*%2B,%3B%3D:@/?%23%25~%E4%B8%AD");
+        assert_eq!(encode(input, &EncodeSet::USERINFO), "a%20b!$&'()*+,;=%3A%40%2F%3F%23%25~%E4%B8%AD");
+        assert_eq!(encode(input, &EncodeSet::FORM), "a+b%21%24%26%27%28%29*%2B%2C%3B%3D%3A%40%2F%3F%23%25%7E%E4%B8%AD");
+        assert_eq!(encode("/a b/c?d=%20#e", &EncodeSet::URI), "/a%20b/c?d=%20#e");
+        assert_eq!(encode([0x00, 0x7f, 0xff], &EncodeSet::URI), "%00%7F%FF");
+    }
+
+    #[test]
+    fn test_decode() {
+        for set in [EncodeSet::COMPONENT, EncodeSet::PATH_SEGMENT, EncodeSet::QUERY, EncodeSet::USERINFO] {
+            let input = "路径 /?#[]@!$&'()*+,;=%";
+            assert_eq!(decode(&encode(input, &set)).unwrap(), input);
+        }
+        assert_eq!(form_decode(&encode("a+b c", &EncodeSet::FORM)).unwrap(), "a+b c");
+        assert_eq!(decode("a+b").unwrap(), "a+b");
+        assert_eq!(decode_bytes("%"), Err(DecodeError::InvalidByte { offset: 0, byte: b'%' }));
+        assert_eq!(decode_bytes("%a"), Err(DecodeError::InvalidByte { offset: 0, byte: b'%' }));
+        assert_eq!(decode_bytes("%zz"), Err(DecodeError::InvalidByte { offset: 1, byte: b'z' }));
+        assert!(decode("%E4%B8").is_err());
+    }
+}
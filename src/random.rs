@@ -1,3 +1,4 @@
+use std::io;
 use crate::timestamp;
 
 /// 基于异或位移实现
@@ -89,6 +90,64 @@ pub fn range(min: u64, max: u64) -> u64 {
     XorShiftRng::new(None).gen_range(min, max)
 }
 
+/// Fill buffer with cryptographically secure random bytes (操作系统提供的安全随机数)
+///
+/// 适用于密钥、盐值、令牌等场景；`str`、`num` 等函数基于时间戳种子，不可用于此类场景
+///
+/// # Example
+/// ```no_run
+/// use light_tool::random;
+/// let mut key = [0u8; 32];
+/// random::fill_secure(&mut key).unwrap();
+/// println!("random key: {:?}", key)
+/// ```
+pub fn fill_secure(buf: &mut [u8]) -> io::Result<()> {
+    os_random(buf)
+}
+
+/// Generate cryptographically secure random bytes
+///
+/// # Example
+/// ```no_run
+/// use light_tool::random;
+/// println!("random bytes: {:?}", random::secure_bytes(16).unwrap())
+/// ```
+pub fn secure_bytes(len: usize) -> io::Result<Vec<u8>> {
+    let mut buf = vec![0u8; len];
+    os_random(&mut buf)?;
+    Ok(buf)
+}
+
+#[cfg(unix)]
+fn os_random(buf: &mut [u8]) -> io::Result<()> {
+    use std::io::Read;
+    std::fs::File::open("/dev/urandom")?.read_exact(buf)
+}
+
+#[cfg(windows)]
+fn os_random(buf: &mut [u8]) -> io::Result<()> {
+    #[link(name = "bcrypt")]
+    extern "system" {
+        fn BCryptGenRandom(algorithm: *mut std::ffi::c_void, buf: *mut u8, len: u32, flags: u32) -> i32;
+    }
+    const BCRYPT_USE_SYSTEM_PREFERRED_RNG: u32 = 0x00000002;
+
+    for chunk in buf.chunks_mut(u32::MAX as usize) {
+        let status = unsafe {
+            BCryptGenRandom(std::ptr::null_mut(), chunk.as_mut_ptr(), chunk.len() as u32, BCRYPT_USE_SYSTEM_PREFERRED_RNG)
+        };
+        if status != 0 {
+            return Err(io::Error::other(format!("BCryptGenRandom failed: {:#x}", status)));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(any(unix, windows)))]
+fn os_random(_buf: &mut [u8]) -> io::Result<()> {
+    Err(io::Error::new(io::ErrorKind::Unsupported, "no secure random source on this platform"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -117,4 +176,12 @@ mod tests {
     fn test_random_range() {
         println!("random_range: {}", range(1, 3));
     }
+
+    #[test]
+    fn test_secure_bytes() {
+        let a = secure_bytes(32).unwrap();
+        let b = secure_bytes(32).unwrap();
+        assert_eq!(a.len(), 32);
+        assert_ne!(a, b);
+    }
 }
@@ -0,0 +1,373 @@
+use crate::digest::Digest;
+
+/// Keccak-f[1600] 轮常量
+const RC: [u64; 24] = [
+    0x0000000000000001, 0x0000000000008082, 0x800000000000808a, 0x8000000080008000,
+    0x000000000000808b, 0x0000000080000001, 0x8000000080008081, 0x8000000000008009,
+    0x000000000000008a, 0x0000000000000088, 0x0000000080008009, 0x000000008000000a,
+    0x000000008000808b, 0x800000000000008b, 0x8000000000008089, 0x8000000000008003,
+    0x8000000000008002, 0x8000000000000080, 0x000000000000800a, 0x800000008000000a,
+    0x8000000080008081, 0x8000000000008080, 0x0000000080000001, 0x8000000080008008,
+];
+
+/// ρ 步骤的循环位移量（按 π 步骤的遍历顺序）
+const RHO: [u32; 24] = [
+    1, 3, 6, 10, 15, 21, 28, 36, 45, 55, 2, 14, 27, 41, 56, 8, 25, 43, 62, 18, 39, 61, 20, 44,
+];
+
+/// π 步骤的遍历顺序
+const PI: [usize; 24] = [
+    10, 7, 11, 17, 18, 3, 5, 16, 8, 21, 24, 4, 15, 23, 19, 13, 12, 2, 20, 14, 22, 9, 6, 1,
+];
+
+/// Keccak-f[1600] 置换
+fn keccak_f(a: &mut [u64; 25]) {
+    for rc in RC {
+        // θ
+        let mut c = [0u64; 5];
+        for x in 0..5 {
+            c[x] = a[x] ^ a[x + 5] ^ a[x + 10] ^ a[x + 15] ^ a[x + 20];
+        }
+        for x in 0..5 {
+            let d = c[(x + 4) % 5] ^ c[(x + 1) % 5].rotate_left(1);
+            for y in 0..5 {
+                a[x + 5 * y] ^= d;
+            }
+        }
+
+        // ρ 与 π
+        let mut last = a[1];
+        for i in 0..24 {
+            let j = PI[i];
+            let temp = a[j];
+            a[j] = last.rotate_left(RHO[i]);
+            last = temp;
+        }
+
+        // χ
+        for y in 0..5 {
+            let row = [a[5 * y], a[5 * y + 1], a[5 * y + 2], a[5 * y + 3], a[5 * y + 4]];
+            for x in 0..5 {
+                a[x + 5 * y] = row[x] ^ (!row[(x + 1) % 5] & row[(x + 2) % 5]);
+            }
+        }
+
+        // ι
+        a[0] ^= rc;
+    }
+}
+
+/// Keccak 海绵结构
+#[derive(Clone)]
+struct Sponge {
+    state: [u64; 25],
+    rate: usize,     // 每次吸收/挤出的字节数
+    pos: usize,      // 当前分组内的位置
+    suffix: u8,      // 域分隔后缀：SHA-3 为 0x06，SHAKE 为 0x1f
+}
+
+impl Sponge {
+    fn new(rate: usize, suffix: u8) -> Self {
+        Self { state: [0u64; 25], rate, pos: 0, suffix }
+    }
+
+    fn xor_byte(&mut self, i: usize, byte: u8) {
+        self.state[i / 8] ^= (byte as u64) << (8 * (i % 8));
+    }
+
+    fn absorb(&mut self, input: &[u8]) {
+        for &byte in input {
+            self.xor_byte(self.pos, byte);
+            self.pos += 1;
+            if self.pos == self.rate {
+                keccak_f(&mut self.state);
+                self.pos = 0;
+            }
+        }
+    }
+
+    /// 填充（pad10*1）并进入挤出阶段
+    fn pad(&mut self) {
+        self.xor_byte(self.pos, self.suffix);
+        self.xor_byte(self.rate - 1, 0x80);
+        keccak_f(&mut self.state);
+        self.pos = 0;
+    }
+
+    fn squeeze(&mut self, output: &mut [u8]) {
+        for byte in output {
+            if self.pos == self.rate {
+                keccak_f(&mut self.state);
+                self.pos = 0;
+            }
+            *byte = (self.state[self.pos / 8] >> (8 * (self.pos % 8))) as u8;
+            self.pos += 1;
+        }
+    }
+}
+
+macro_rules! sha3 {
+    ($name:ident, $output:expr, $rate:expr, $doc:expr) => {
+        #[doc = $doc]
+        #[derive(Clone)]
+        pub struct $name {
+            sponge: Sponge,
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl $name {
+            /// 初始化哈希状态
+            pub fn new() -> Self {
+                Self { sponge: Sponge::new($rate, 0x06) }
+            }
+
+            /// 写入数据
+            pub fn update(&mut self, input: &[u8]) {
+                self.sponge.absorb(input);
+            }
+
+            /// 计算最终的哈希值
+            pub fn finalize(mut self) -> [u8; $output] {
+                let mut digest = [0u8; $output];
+                self.sponge.pad();
+                self.sponge.squeeze(&mut digest);
+                digest
+            }
+        }
+
+        crate::digest::impl_digest!($name, $output, $rate);
+    };
+}
+
+sha3!(Sha3_224, 28, 144, "SHA3-224 结构体");
+sha3!(Sha3_256, 32, 136, "SHA3-256 结构体");
+sha3!(Sha3_384, 48, 104, "SHA3-384 结构体");
+sha3!(Sha3_512, 64, 72, "SHA3-512 结构体");
+
+macro_rules! shake {
+    ($name:ident, $rate:expr, $doc:expr) => {
+        #[doc = $doc]
+        #[derive(Clone)]
+        pub struct $name {
+            sponge: Sponge,
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl $name {
+            /// 初始化状态
+            pub fn new() -> Self {
+                Self { sponge: Sponge::new($rate, 0x1f) }
+            }
+
+            /// 写入数据
+            pub fn update(&mut self, input: &[u8]) {
+                self.sponge.absorb(input);
+            }
+
+            /// 结束写入，返回可以读取任意长度输出的读取器
+            pub fn finalize_xof(mut self) -> XofReader {
+                self.sponge.pad();
+                XofReader { sponge: self.sponge }
+            }
+
+            /// 结束写入，输出 `len` 字节
+            pub fn finalize(self, len: usize) -> Vec<u8> {
+                let mut output = vec![0u8; len];
+                self.finalize_xof().read(&mut output);
+                output
+            }
+        }
+
+        impl std::io::Write for $name {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.update(buf);
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+    };
+}
+
+shake!(Shake128, 168, "SHAKE128 可扩展输出函数");
+shake!(Shake256, 136, "SHAKE256 可扩展输出函数");
+
+/// SHAKE 输出读取器，可多次读取，输出连续
+///
+/// # Example
+/// ```rust
+/// use light_tool::sha3::Shake256;
+/// let mut shake = Shake256::new();
+/// shake.update(b"Hello, world!");
+/// let mut reader = shake.finalize_xof();
+/// let mut token = [0u8; 16];
+/// reader.read(&mut token);
+/// println!("token: {:?}", token)
+/// ```
+#[derive(Clone)]
+pub struct XofReader {
+    sponge: Sponge,
+}
+
+impl XofReader {
+    /// 读取下一段输出
+    pub fn read(&mut self, output: &mut [u8]) {
+        self.sponge.squeeze(output);
+    }
+}
+
+impl std::io::Read for XofReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.sponge.squeeze(buf);
+        Ok(buf.len())
+    }
+}
+
+/// SHA3-224 哈希函数
+///
+/// # Example
+/// ```rust
+/// use light_tool::sha3;
+/// println!("sha3-224: {}", sha3::sha3_224("Hello, world!"))
+/// ```
+pub fn sha3_224<T>(input: T) -> String
+where
+    T: AsRef<[u8]>,
+{
+    Sha3_224::hex_digest(input)
+}
+
+/// SHA3-256 哈希函数
+///
+/// # Example
+/// ```rust
+/// use light_tool::sha3;
+/// println!("sha3-256: {}", sha3::sha3_256("Hello, world!"))
+/// ```
+pub fn sha3_256<T>(input: T) -> String
+where
+    T: AsRef<[u8]>,
+{
+    Sha3_256::hex_digest(input)
+}
+
+/// SHA3-384 哈希函数
+///
+/// # Example
+/// ```rust
+/// use light_tool::sha3;
+/// println!("sha3-384: {}", sha3::sha3_384("Hello, world!"))
+/// ```
+pub fn sha3_384<T>(input: T) -> String
+where
+    T: AsRef<[u8]>,
+{
+    Sha3_384::hex_digest(input)
+}
+
+/// SHA3-512 哈希函数
+///
+/// # Example
+/// ```rust
+/// use light_tool::sha3;
+/// println!("sha3-512: {}", sha3::sha3_512("Hello, world!"))
+/// ```
+pub fn sha3_512<T>(input: T) -> String
+where
+    T: AsRef<[u8]>,
+{
+    Sha3_512::hex_digest(input)
+}
+
+/// SHAKE128，输出 `len` 字节
+///
+/// # Example
+/// ```rust
+/// use light_tool::sha3;
+/// println!("shake128: {:?}", sha3::shake128("Hello, world!", 16))
+/// ```
+pub fn shake128<T>(input: T, len: usize) -> Vec<u8>
+where
+    T: AsRef<[u8]>,
+{
+    let mut shake = Shake128::new();
+    shake.update(input.as_ref());
+    shake.finalize(len)
+}
+
+/// SHAKE256，输出 `len` 字节
+///
+/// # Example
+/// ```rust
+/// use light_tool::sha3;
+/// println!("shake256: {:?}", sha3::shake256("Hello, world!", 32))
+/// ```
+pub fn shake256<T>(input: T, len: usize) -> Vec<u8>
+where
+    T: AsRef<[u8]>,
+{
+    let mut shake = Shake256::new();
+    shake.update(input.as_ref());
+    shake.finalize(len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hex::encode as hex;
+
+    #[test]
+    fn test_sha3() {
+        assert_eq!(sha3_224(""), "6b4e03423667dbb73b6e15454f0eb1abd4597f9a1b078e3f5b5a6bc7");
+        assert_eq!(sha3_256(""), "a7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a");
+        assert_eq!(sha3_256("abc"), "3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532");
+        assert_eq!(
+            sha3_384("abc"),
+            "ec01498288516fc926459f58e2c6ad8df9b473cb0fc08c2596da7cf0e49be4b298d88cea927ac7f539f1edf228376d25"
+        );
+        assert_eq!(
+            sha3_512("abc"),
+            "b751850b1a57168a5693cd924b6b096e08f621827444f70d884f5d0240d2712e10e116e9192af3c91a7ec57647e3934057340b4cf408d5a56592f8274eec53f0"
+        );
+    }
+
+    #[test]
+    fn test_sha3_streaming() {
+        // 分组长度即各算法的 rate：144、136、104、72 字节
+        crate::digest::assert_streaming::<Sha3_224>();
+        crate::digest::assert_streaming::<Sha3_256>();
+        crate::digest::assert_streaming::<Sha3_384>();
+        crate::digest::assert_streaming::<Sha3_512>();
+    }
+
+    #[test]
+    fn test_shake() {
+        assert_eq!(hex(shake128("", 32)), "7f9c2ba4e88f827d616045507605853ed73b8093f6efbc88eb1a6eacfa66ef26");
+        assert_eq!(
+            hex(shake256("", 64)),
+            "46b9dd2b0ba88d13233b3feb743eeb243fcd52ea62b81b82b50c27646ed5762fd75dc4ddd8c0f200cb05019d67b592f6fc821c49479ab48640292eacb3b7c4be"
+        );
+
+        // 分多次读取与一次读取的结果一致
+        let mut shake = Shake128::new();
+        shake.update(b"abc");
+        let mut reader = shake.finalize_xof();
+        let mut output = vec![0u8; 400];
+        for chunk in output.chunks_mut(7) {
+            reader.read(chunk);
+        }
+        assert_eq!(output, shake128("abc", 400));
+    }
+}
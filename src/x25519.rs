@@ -0,0 +1,172 @@
+use std::io;
+use crate::curve25519::{self, impl_key_encoding, Fe, ONE, ZERO};
+use crate::random;
+
+/// 基点 u = 9
+pub const BASEPOINT: [u8; 32] = [9, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+
+/// (A - 2) / 4
+const A24: Fe = Fe([0xdb41, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+
+/// X25519 函数 (RFC 7748)，计算标量与 u 坐标的乘积
+///
+/// # Example
+/// ```rust
+/// use light_tool::x25519;
+/// let public = x25519::x25519(&[1; 32], &x25519::BASEPOINT);
+/// assert_eq!(public.len(), 32)
+/// ```
+pub fn x25519(scalar: &[u8; 32], u: &[u8; 32]) -> [u8; 32] {
+    let mut k = *scalar;
+    k[0] &= 248;
+    k[31] &= 127;
+    k[31] |= 64;
+
+    // Montgomery 阶梯，每一位执行相同运算
+    let x1 = Fe::from_bytes(u);
+    let (mut x2, mut z2, mut x3, mut z3) = (ONE, ZERO, x1, ONE);
+    for i in (0..255).rev() {
+        let bit = ((k[i / 8] >> (i & 7)) & 1) as i64;
+        Fe::swap(&mut x2, &mut x3, bit);
+        Fe::swap(&mut z2, &mut z3, bit);
+
+        let a = x2 + z2;
+        let aa = a.square();
+        let b = x2 - z2;
+        let bb = b.square();
+        let e = aa - bb;
+        let c = x3 + z3;
+        let d = x3 - z3;
+        let da = d * a;
+        let cb = c * b;
+        x3 = (da + cb).square();
+        z3 = x1 * (da - cb).square();
+        x2 = aa * bb;
+        z2 = e * (aa + A24 * e);
+
+        Fe::swap(&mut x2, &mut x3, bit);
+        Fe::swap(&mut z2, &mut z3, bit);
+    }
+    (x2 * z2.invert()).to_bytes()
+}
+
+/// X25519 私钥
+///
+/// # Example
+/// ```rust
+/// use light_tool::x25519::{PublicKey, SecretKey};
+/// let alice = SecretKey::generate().unwrap();
+/// let bob = SecretKey::generate().unwrap();
+///
+/// // 交换 Base64 编码的公钥
+/// let alice_public = PublicKey::from_base64(&alice.public_key().to_base64()).unwrap();
+/// let bob_public = PublicKey::from_base64(&bob.public_key().to_base64()).unwrap();
+/// assert_eq!(alice.diffie_hellman(&bob_public).unwrap(), bob.diffie_hellman(&alice_public).unwrap())
+/// ```
+#[derive(Clone)]
+pub struct SecretKey([u8; 32]);
+
+impl SecretKey {
+    /// 使用安全随机数生成私钥
+    pub fn generate() -> io::Result<Self> {
+        let mut bytes = [0u8; 32];
+        random::fill_secure(&mut bytes)?;
+        Ok(SecretKey(bytes))
+    }
+
+    /// 从 32 字节导入
+    pub fn from_bytes(bytes: &[u8; 32]) -> Self {
+        SecretKey(*bytes)
+    }
+
+    /// 从任意长度的切片导入，长度必须为 32
+    pub fn from_slice(bytes: &[u8]) -> Result<Self, String> {
+        Ok(SecretKey(curve25519::to_array(bytes, "X25519 secret key")?))
+    }
+
+    /// 导出 32 字节
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.0
+    }
+
+    /// 对应的公钥
+    pub fn public_key(&self) -> PublicKey {
+        PublicKey(x25519(&self.0, &BASEPOINT))
+    }
+
+    /// 计算共享密钥；对方公钥为低阶点（结果全零）时返回错误
+    ///
+    /// 共享密钥不宜直接用作加密密钥，应先经过 `kdf::hkdf` 派生
+    pub fn diffie_hellman(&self, public: &PublicKey) -> Result<[u8; 32], String> {
+        let shared = x25519(&self.0, &public.0);
+        if shared.iter().fold(0, |acc, b| acc | b) == 0 {
+            return Err("Invalid X25519 public key: low order point".to_string());
+        }
+        Ok(shared)
+    }
+}
+
+impl_key_encoding!(SecretKey);
+
+/// X25519 公钥
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PublicKey([u8; 32]);
+
+impl PublicKey {
+    /// 从 32 字节导入
+    pub fn from_bytes(bytes: &[u8; 32]) -> Self {
+        PublicKey(*bytes)
+    }
+
+    /// 从任意长度的切片导入，长度必须为 32
+    pub fn from_slice(bytes: &[u8]) -> Result<Self, String> {
+        Ok(PublicKey(curve25519::to_array(bytes, "X25519 public key")?))
+    }
+
+    /// 导出 32 字节
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.0
+    }
+}
+
+impl_key_encoding!(PublicKey);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(input: &str) -> [u8; 32] {
+        crate::hex::decode(input).unwrap().try_into().unwrap()
+    }
+
+    #[test]
+    fn test_rfc7748() {
+        // §5.2
+        assert_eq!(
+            x25519(
+                &hex("a546e36bf0527c9d3b16154b82465edd62144c0ac1fc5a18506a2244ba449ac4"),
+                &hex("e6db6867583030db3594c1a424b15f7c726624ec26b3353b10a903a6d0ab1c4c"),
+            ),
+            hex("c3da55379de9c6908e94ea4df28d084f32eccf03491c71f754b4075577a28552")
+        );
+
+        // §5.2 迭代 1 次
+        assert_eq!(x25519(&BASEPOINT, &BASEPOINT), hex("422c8e7a6227d7bca1350b3e2bb7279f7897b87bb6854b783c60e80311ae3079"));
+    }
+
+    #[test]
+    fn test_diffie_hellman() {
+        // §6.1
+        let alice = SecretKey::from_hex("77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a").unwrap();
+        let bob = SecretKey::from_hex("5dab087e624a8a4b79e17f8b83800ee66f3bb1292618b6fd1c2f8b27ff88e0eb").unwrap();
+        assert_eq!(alice.public_key().to_hex(), "8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a");
+        assert_eq!(bob.public_key().to_hex(), "de9edb7d7b7dc1b4d35b61c2ece435373f8343c85b78674dadfc7e146f882b4f");
+
+        let shared = hex("4a5d9d5ba4ce2de1728e3bf480350f25e07e21c947d19e3376f09b3c1e161742");
+        assert_eq!(alice.diffie_hellman(&bob.public_key()).unwrap(), shared);
+        assert_eq!(bob.diffie_hellman(&alice.public_key()).unwrap(), shared);
+
+        assert!(alice.diffie_hellman(&PublicKey::from_bytes(&[0; 32])).is_err());
+        assert!(PublicKey::from_slice(&[0; 33]).is_err());
+    }
+}